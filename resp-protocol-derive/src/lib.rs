@@ -0,0 +1,407 @@
+//! Derive macros for the `ToResp` and `FromResp` traits of `resp-protocol`
+//!
+//! Enabled through the `derive` feature of `resp-protocol`, which re-exports them.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use std::collections::HashSet;
+use syn::ext::IdentExt;
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, GenericParam, Generics,
+    Ident, LitByteStr, LitStr, Result, Type,
+};
+
+/// Derive `ToResp` for a struct
+///
+/// A struct with named fields is written as a Map keyed by field names, or as an Array of keys
+/// and values in RESP2. A tuple struct is written as an Array of its fields, and a newtype as
+/// its single field.
+///
+/// # Attributes
+/// - `#[resp(flatten)]` on the struct writes an Array of keys and values in RESP3 too
+/// - `#[resp(rename = "name")]` on a field changes its key
+/// - `#[resp(skip)]` on a field leaves it out
+///
+/// # Example
+/// ``` rust
+/// use resp_protocol::{Protocol, ToResp};
+///
+/// #[derive(ToResp)]
+/// struct User {
+///     #[resp(rename = "user-name")]
+///     name: String,
+///     age: i64,
+///     #[resp(skip)]
+///     password: String,
+/// }
+///
+/// let user = User { name: "ann".to_owned(), age: 30, password: "secret".to_owned() };
+/// assert_eq!(
+///     user.to_resp(Protocol::Resp3).bytes(),
+///     &b"%2\r\n$9\r\nuser-name\r\n$3\r\nann\r\n$3\r\nage\r\n:30\r\n"[..]
+/// );
+/// assert_eq!(
+///     user.to_resp(Protocol::Resp2).bytes(),
+///     &b"*4\r\n$9\r\nuser-name\r\n$3\r\nann\r\n$3\r\nage\r\n:30\r\n"[..]
+/// );
+/// ```
+#[proc_macro_derive(ToResp, attributes(resp))]
+pub fn derive_to_resp(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_to_resp(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Derive `FromResp` for a struct
+///
+/// A struct with named fields is read from a Map, or from an Array of keys and values, where
+/// unknown keys are ignored and absent `Option` fields are `None`. A tuple struct is read from
+/// an Array, a Set or a Push of exactly its fields, and a newtype from its single field.
+///
+/// Takes the same attributes as `ToResp`. A skipped field is set to its `Default`.
+///
+/// # Example
+/// ``` rust
+/// use bytes::Bytes;
+/// use resp_protocol::{FromResp, RespType};
+///
+/// #[derive(Debug, PartialEq, FromResp)]
+/// struct User {
+///     #[resp(rename = "user-name")]
+///     name: String,
+///     age: Option<i64>,
+///     #[resp(skip)]
+///     password: String,
+/// }
+///
+/// let input = Bytes::from_static(b"*2\r\n$9\r\nuser-name\r\n$3\r\nann\r\n");
+/// let value = RespType::parse_bytes(&input, &mut 0).unwrap();
+/// assert_eq!(
+///     User::from_resp(&value),
+///     Ok(User { name: "ann".to_owned(), age: None, password: String::new() })
+/// );
+/// ```
+#[proc_macro_derive(FromResp, attributes(resp))]
+pub fn derive_from_resp(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_from_resp(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+struct Field {
+    member: TokenStream2,
+    variable: Ident,
+    name: Option<String>,
+    ty: Type,
+    skip: bool,
+}
+
+struct Container {
+    flatten: bool,
+    named: bool,
+    fields: Vec<Field>,
+}
+
+impl Container {
+    fn from_input(input: &DeriveInput, derive: &str) -> Result<Self> {
+        let fields = match &input.data {
+            Data::Struct(data) => &data.fields,
+            Data::Enum(_) | Data::Union(_) => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    format!("{} can only be derived for structs", derive),
+                ));
+            }
+        };
+        let named = match fields {
+            Fields::Named(_) => true,
+            Fields::Unnamed(_) => false,
+            Fields::Unit => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    format!("{} cannot be derived for unit structs", derive),
+                ));
+            }
+        };
+
+        let mut flatten = false;
+        for attr in input
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("resp"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("flatten") {
+                    flatten = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `flatten`"))
+                }
+            })?;
+        }
+        if flatten && !named {
+            return Err(Error::new(
+                Span::call_site(),
+                "`flatten` only applies to structs with named fields",
+            ));
+        }
+
+        let mut names = HashSet::new();
+        let mut container_fields = Vec::with_capacity(fields.len());
+        for (index, field) in fields.iter().enumerate() {
+            let mut rename = None;
+            let mut skip = false;
+            for attr in field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("resp"))
+            {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("rename") {
+                        if field.ident.is_none() {
+                            return Err(meta.error("`rename` only applies to named fields"));
+                        }
+                        rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                        Ok(())
+                    } else if meta.path.is_ident("skip") {
+                        skip = true;
+                        Ok(())
+                    } else {
+                        Err(meta.error("expected `rename` or `skip`"))
+                    }
+                })?;
+            }
+
+            let (member, variable, name) = match &field.ident {
+                Some(ident) => {
+                    let name = rename.unwrap_or_else(|| ident.unraw().to_string());
+                    if !skip && !names.insert(name.clone()) {
+                        return Err(Error::new_spanned(
+                            ident,
+                            format!("duplicate field name `{}`", name),
+                        ));
+                    }
+                    (
+                        quote!(#ident),
+                        format_ident!("__field_{}", ident.unraw()),
+                        Some(name),
+                    )
+                }
+                None => {
+                    let member = syn::Index::from(index);
+                    (quote!(#member), format_ident!("__field_{}", index), None)
+                }
+            };
+            container_fields.push(Field {
+                member,
+                variable,
+                name,
+                ty: field.ty.clone(),
+                skip,
+            });
+        }
+
+        Ok(Self {
+            flatten,
+            named,
+            fields: container_fields,
+        })
+    }
+
+    fn fields(&self) -> impl Iterator<Item = &Field> {
+        self.fields.iter().filter(|field| !field.skip)
+    }
+
+    /// The single field of a newtype, written and read as itself
+    fn newtype(&self) -> Option<&Field> {
+        let mut fields = self.fields();
+        match (self.named, fields.next(), fields.next()) {
+            (false, Some(field), None) => Some(field),
+            _ => None,
+        }
+    }
+}
+
+fn add_bounds(mut generics: Generics, bound: TokenStream2) -> Generics {
+    let params: Vec<Ident> = generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(param) => Some(param.ident.clone()),
+            _ => None,
+        })
+        .collect();
+    let where_clause = generics.make_where_clause();
+    for param in params {
+        where_clause.predicates.push(parse_quote!(#param: #bound));
+    }
+    generics
+}
+
+fn expand_to_resp(input: DeriveInput) -> Result<TokenStream2> {
+    let container = Container::from_input(&input, "ToResp")?;
+    let ident = &input.ident;
+    let generics = add_bounds(input.generics.clone(), quote!(::resp_protocol::ToResp));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = if let Some(field) = container.newtype() {
+        let member = &field.member;
+        quote! {
+            ::resp_protocol::ToResp::to_resp(&self.#member, protocol)
+        }
+    } else if container.named {
+        let entries = container.fields().map(|field| {
+            let member = &field.member;
+            let key = LitByteStr::new(
+                field.name.as_deref().unwrap_or_default().as_bytes(),
+                Span::call_site(),
+            );
+            quote! {
+                (
+                    ::resp_protocol::ToResp::to_resp(&#key[..], protocol),
+                    ::resp_protocol::ToResp::to_resp(&self.#member, protocol),
+                )
+            }
+        });
+        let map_protocol = if container.flatten {
+            quote!(::resp_protocol::Protocol::Resp2)
+        } else {
+            quote!(protocol)
+        };
+        quote! {
+            ::resp_protocol::__private::map(#map_protocol, ::std::vec![#(#entries),*])
+        }
+    } else {
+        let values = container.fields().map(|field| {
+            let member = &field.member;
+            quote! {
+                ::resp_protocol::ToResp::to_resp(&self.#member, protocol)
+            }
+        });
+        quote! {
+            ::resp_protocol::__private::array(::std::vec![#(#values),*])
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::resp_protocol::ToResp for #ident #ty_generics #where_clause {
+            fn to_resp(&self, protocol: ::resp_protocol::Protocol) -> ::resp_protocol::RespType {
+                #body
+            }
+        }
+    })
+}
+
+fn expand_from_resp(input: DeriveInput) -> Result<TokenStream2> {
+    let container = Container::from_input(&input, "FromResp")?;
+    let ident = &input.ident;
+    let expected = ident.unraw().to_string();
+    let generics = add_bounds(input.generics.clone(), quote!(::resp_protocol::FromResp));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = if container.named {
+        let declarations = container.fields().map(|field| {
+            let variable = &field.variable;
+            let ty = &field.ty;
+            quote! {
+                let mut #variable: ::std::option::Option<#ty> = ::std::option::Option::None;
+            }
+        });
+        let arms = container.fields().map(|field| {
+            let variable = &field.variable;
+            let key = LitByteStr::new(
+                field.name.as_deref().unwrap_or_default().as_bytes(),
+                Span::call_site(),
+            );
+            quote! {
+                #key => {
+                    #variable = ::std::option::Option::Some(
+                        ::resp_protocol::FromResp::from_resp(&__value)
+                            .map_err(|error| error.nested_in_path(::std::iter::once(__index)))?,
+                    );
+                }
+            }
+        });
+        let members = container.fields.iter().map(|field| {
+            let member = &field.member;
+            if field.skip {
+                return quote!(#member: ::std::default::Default::default());
+            }
+            let variable = &field.variable;
+            let missing = format!("field `{}`", field.name.as_deref().unwrap_or_default());
+            quote! {
+                #member: match #variable {
+                    ::std::option::Option::Some(value) => value,
+                    ::std::option::Option::None => ::resp_protocol::__private::missing(#missing)?,
+                }
+            }
+        });
+        let fields = if container.fields().next().is_some() {
+            quote! {
+                #(#declarations)*
+                for (__index, __key, __value) in ::resp_protocol::__private::fields(#expected, value)? {
+                    match &__key[..] {
+                        #(#arms)*
+                        _ => {}
+                    }
+                }
+            }
+        } else {
+            quote! {
+                ::resp_protocol::__private::fields(#expected, value)?;
+            }
+        };
+        quote! {
+            #fields
+            ::std::result::Result::Ok(#ident { #(#members),* })
+        }
+    } else {
+        let newtype = container.newtype().is_some();
+        let count = container.fields().count();
+        let mut position = 0_usize;
+        let members = container.fields.iter().map(|field| {
+            let member = &field.member;
+            if field.skip {
+                quote!(#member: ::std::default::Default::default())
+            } else if newtype {
+                quote!(#member: ::resp_protocol::FromResp::from_resp(value)?)
+            } else {
+                let index = position;
+                position += 1;
+                quote! {
+                    #member: ::resp_protocol::FromResp::from_resp(&__values[#index])
+                        .map_err(|error| error.nested_in_path(::std::iter::once(#index)))?
+                }
+            }
+        });
+        let values = if newtype {
+            quote!()
+        } else if count == 0 {
+            quote! {
+                ::resp_protocol::__private::elements(#expected, 0, value)?;
+            }
+        } else {
+            quote! {
+                let __values = ::resp_protocol::__private::elements(#expected, #count, value)?;
+            }
+        };
+        let members: Vec<TokenStream2> = members.collect();
+        quote! {
+            #values
+            ::std::result::Result::Ok(#ident { #(#members),* })
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::resp_protocol::FromResp for #ident #ty_generics #where_clause {
+            fn from_resp(
+                value: &::resp_protocol::RespType,
+            ) -> ::std::result::Result<Self, ::resp_protocol::FromRespError> {
+                #body
+            }
+        }
+    })
+}
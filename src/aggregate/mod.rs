@@ -0,0 +1,523 @@
+use crate::{length, Limits, RespError, RespErrorKind, RespType};
+
+/// Check whether a type byte starts an aggregate value
+#[inline]
+pub(crate) fn is_aggregate(first_char: u8) -> bool {
+    matches!(first_char, 0x2a | 0x25 | 0x7e | 0x3e | 0x7c)
+}
+
+/// Validate the `<type><count>\r\n` header of an aggregate and return the number of values following it
+///
+/// A Map holds a key and a value per entry, and an Attribute is followed by the reply it describes.
+/// A Null Array holds no values. Returns `None` for the `<type>?\r\n` header of a streamed
+/// Array, Map or Set, whose values are followed by `.\r\n` instead.
+pub(crate) fn while_valid_header(
+    first_char: u8,
+    input: &[u8],
+    start: &mut usize,
+    end: &usize,
+    limits: &Limits,
+) -> Result<Option<usize>, RespError> {
+    let mut index = *start;
+    if index >= *end {
+        return Err(RespError::incomplete(None, input, end).within(first_char));
+    }
+    if input[index] != first_char || !is_aggregate(first_char) {
+        return Err(
+            RespError::new(RespErrorKind::InvalidFirstChar, input, end, index).within(first_char),
+        );
+    }
+    index += 1;
+    if index >= *end {
+        return Err(RespError::incomplete(None, input, end).within(first_char));
+    }
+    if first_char == 0x2a && input[index] == 0x2d {
+        length::while_valid_token(
+            b"-1\r\n",
+            RespErrorKind::InvalidNullValue,
+            input,
+            &mut index,
+            end,
+        )
+        .map_err(|error| error.within(first_char))?;
+        *start = index;
+        return Ok(Some(0));
+    }
+    if matches!(first_char, 0x2a | 0x25 | 0x7e) && input[index] == 0x3f {
+        length::while_valid_token(
+            b"?\r\n",
+            RespErrorKind::InvalidLength,
+            input,
+            &mut index,
+            end,
+        )
+        .map_err(|error| error.within(first_char))?;
+        *start = index;
+        return Ok(None);
+    }
+    let length_start_index = index;
+    let length =
+        length::while_valid(input, &mut index, end).map_err(|error| error.within(first_char))?;
+    length::while_valid_token(
+        b"\r\n",
+        RespErrorKind::InvalidLengthSeparator,
+        input,
+        &mut index,
+        end,
+    )
+    .map_err(|error| error.within(first_char))?;
+    limits
+        .check_element_count(length, input, end, length_start_index)
+        .map_err(|error| error.within(first_char))?;
+    let count = match first_char {
+        0x25 => length.checked_mul(2),
+        0x7c => length.checked_mul(2).and_then(|count| count.checked_add(1)),
+        _ => Some(length),
+    };
+    let count = count.ok_or_else(|| {
+        RespError::new(RespErrorKind::InvalidLength, input, end, length_start_index)
+            .within(first_char)
+    })?;
+    *start = index;
+    Ok(Some(count))
+}
+
+/// Number of elements of an aggregate holding `count` values, counting entries for a Map
+#[inline]
+pub(crate) fn entries(first_char: u8, count: usize) -> usize {
+    if first_char == 0x25 {
+        count / 2 + count % 2
+    } else {
+        count
+    }
+}
+
+/// Validate the `.\r\n` ending a streamed aggregate after `count` values
+pub(crate) fn while_valid_streamed_end(
+    first_char: u8,
+    count: usize,
+    input: &[u8],
+    start: &mut usize,
+    end: &usize,
+) -> Result<(), RespError> {
+    if first_char == 0x25 && count % 2 == 1 {
+        return Err(
+            RespError::new(RespErrorKind::LengthsNotMatch, input, end, *start).within(first_char),
+        );
+    }
+    length::while_valid_token(b".\r\n", RespErrorKind::InvalidTerminate, input, start, end)
+        .map_err(|error| error.within(first_char))
+}
+
+/// Aggregate opened while validating, whose values are not all validated yet
+#[derive(Clone, Copy)]
+struct Open {
+    first_char: u8,
+    /// Number of values, `None` for a streamed aggregate.
+    count: Option<usize>,
+    validated: usize,
+}
+
+/// Number of open aggregates held inline before the stack moves to the heap
+const INLINE_DEPTH: usize = 16;
+
+/// Open aggregates, innermost last
+///
+/// Held inline up to [`INLINE_DEPTH`] aggregates, so that validating does not allocate
+/// unless aggregates nest deeper than that.
+#[allow(clippy::large_enum_variant)] // the inline variant is the point
+enum Stack {
+    Inline {
+        opens: [Open; INLINE_DEPTH],
+        len: usize,
+    },
+    Heap(Vec<Open>),
+}
+
+impl Stack {
+    const EMPTY: Open = Open {
+        first_char: 0,
+        count: None,
+        validated: 0,
+    };
+
+    #[inline]
+    fn new() -> Self {
+        Stack::Inline {
+            opens: [Self::EMPTY; INLINE_DEPTH],
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, open: Open) {
+        match self {
+            Stack::Inline { opens, len } if *len < INLINE_DEPTH => {
+                opens[*len] = open;
+                *len += 1;
+            }
+            Stack::Inline { opens, .. } => {
+                let mut heap = Vec::with_capacity(2 * INLINE_DEPTH);
+                heap.extend_from_slice(opens);
+                heap.push(open);
+                *self = Stack::Heap(heap);
+            }
+            Stack::Heap(heap) => heap.push(open),
+        }
+    }
+
+    #[inline]
+    fn pop(&mut self) {
+        match self {
+            Stack::Inline { len, .. } => *len = len.saturating_sub(1),
+            Stack::Heap(heap) => {
+                heap.pop();
+            }
+        }
+    }
+}
+
+impl std::ops::Deref for Stack {
+    type Target = [Open];
+
+    #[inline]
+    fn deref(&self) -> &[Open] {
+        match self {
+            Stack::Inline { opens, len } => &opens[..*len],
+            Stack::Heap(heap) => heap,
+        }
+    }
+}
+
+impl std::ops::DerefMut for Stack {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [Open] {
+        match self {
+            Stack::Inline { opens, len } => &mut opens[..*len],
+            Stack::Heap(heap) => heap,
+        }
+    }
+}
+
+/// Validate an aggregate at nesting `depth`, its header followed by all of its values, and
+/// return whether it holds streamed values
+///
+/// Nested aggregates are tracked on an explicit stack rather than by recursion, so nesting
+/// is bounded by `limits` only, never by the size of the thread stack. The stack is held
+/// inline, and only allocates for aggregates nested more than 16 deep.
+pub(crate) fn while_valid(
+    first_char: u8,
+    input: &[u8],
+    start: &mut usize,
+    end: &usize,
+    limits: &Limits,
+    depth: usize,
+) -> Result<bool, RespError> {
+    let mut index = *start;
+    let mut stack = Stack::new();
+    let mut streamed = false;
+    if let Some(open) = while_valid_open(first_char, input, &mut index, end, limits, depth)? {
+        streamed |= open.count.is_none();
+        stack.push(open);
+    }
+    while let Some(open) = stack.last() {
+        let first_char = open.first_char;
+        let validated = open.validated;
+        match open.count {
+            Some(count) if validated == count => {
+                stack.pop();
+                close(&mut stack);
+                continue;
+            }
+            None if index < *end && input[index] == 0x2e => {
+                stack.pop();
+                while_valid_streamed_end(first_char, validated, input, &mut index, end)
+                    .map_err(|error| nest(&stack, error))?;
+                close(&mut stack);
+                continue;
+            }
+            None => {
+                let outer = &stack[..stack.len() - 1];
+                limits
+                    .check_element_count(entries(first_char, validated + 1), input, end, index)
+                    .map_err(|error| nest(outer, error.within(first_char)))?;
+            }
+            Some(_) => {}
+        }
+        if index >= *end {
+            return Err(nest(&stack, RespError::incomplete(None, input, end)));
+        }
+        let value_first_char = input[index];
+        if is_aggregate(value_first_char) {
+            let value_depth = depth + stack.len();
+            match while_valid_open(
+                value_first_char,
+                input,
+                &mut index,
+                end,
+                limits,
+                value_depth,
+            )
+            .map_err(|error| nest(&stack, error))?
+            {
+                Some(open) => {
+                    streamed |= open.count.is_none();
+                    stack.push(open);
+                }
+                None => close(&mut stack),
+            }
+        } else {
+            streamed |= RespType::while_valid_nested(
+                input,
+                &mut index,
+                end,
+                limits,
+                depth + stack.len() - 1,
+            )
+            .map_err(|error| nest(&stack, error))?;
+            close(&mut stack);
+        }
+    }
+    *start = index;
+    Ok(streamed)
+}
+
+/// Validate the header of an aggregate at nesting `depth`, returning it unless it holds no values
+fn while_valid_open(
+    first_char: u8,
+    input: &[u8],
+    start: &mut usize,
+    end: &usize,
+    limits: &Limits,
+    depth: usize,
+) -> Result<Option<Open>, RespError> {
+    limits
+        .check_depth(depth, input, end, *start)
+        .map_err(|error| error.within(first_char))?;
+    let count = match while_valid_header(first_char, input, start, end, limits)? {
+        Some(0) => return Ok(None),
+        count => count,
+    };
+    Ok(Some(Open {
+        first_char,
+        count,
+        validated: 0,
+    }))
+}
+
+/// Count a validated value in the innermost open aggregate
+#[inline]
+fn close(stack: &mut [Open]) {
+    if let Some(open) = stack.last_mut() {
+        open.validated += 1;
+    }
+}
+
+/// Record the path of the value being validated within the open aggregates
+fn nest(stack: &[Open], error: RespError) -> RespError {
+    let last = stack
+        .iter()
+        .all(|open| open.count == Some(open.validated + 1));
+    let error = if last {
+        error
+    } else {
+        error.followed_by_values()
+    };
+    match stack.last() {
+        Some(open) => error
+            .within(open.first_char)
+            .nested_in_path(stack.iter().map(|open| open.validated)),
+        None => error,
+    }
+}
+
+#[cfg(test)]
+mod tests_aggregate {
+    use crate::aggregate::{while_valid, while_valid_header};
+    use crate::{Limits, RespErrorKind};
+
+    #[test]
+    fn test_while_valid_header() {
+        let mut cursor = 0;
+        assert_eq!(
+            while_valid_header(0x2a, b"*3\r\n", &mut cursor, &4, &Limits::new()),
+            Ok(Some(3))
+        );
+        assert_eq!(cursor, 4);
+        let mut cursor = 0;
+        assert_eq!(
+            while_valid_header(0x25, b"%3\r\n", &mut cursor, &4, &Limits::new()),
+            Ok(Some(6))
+        );
+        let mut cursor = 0;
+        assert_eq!(
+            while_valid_header(0x7c, b"|3\r\n", &mut cursor, &4, &Limits::new()),
+            Ok(Some(7))
+        );
+        let mut cursor = 0;
+        assert_eq!(
+            while_valid_header(0x2a, b"*-1\r\n", &mut cursor, &5, &Limits::new()),
+            Ok(Some(0))
+        );
+        assert_eq!(cursor, 5);
+    }
+
+    #[test]
+    fn test_while_valid_header_invalid() {
+        assert_eq!(
+            while_valid_header(0x25, b"%-1\r\n", &mut 0, &5, &Limits::new()).unwrap_err(),
+            RespErrorKind::InvalidLength
+        );
+        assert_eq!(
+            while_valid_header(0x25, b"*1\r\n", &mut 0, &4, &Limits::new()).unwrap_err(),
+            RespErrorKind::InvalidFirstChar
+        );
+        let string = format!("%{}\r\n", usize::MAX);
+        assert_eq!(
+            while_valid_header(
+                0x25,
+                string.as_bytes(),
+                &mut 0,
+                &string.len(),
+                &Limits::unlimited()
+            )
+            .unwrap_err(),
+            RespErrorKind::InvalidLength
+        );
+    }
+
+    #[test]
+    fn test_while_valid_streamed() {
+        let string = "*?\r\n:1\r\n*?\r\n.\r\n$?\r\n;1\r\na\r\n;0\r\n.\r\n";
+        let mut cursor = 0;
+        assert_eq!(
+            while_valid(
+                0x2a,
+                string.as_bytes(),
+                &mut cursor,
+                &string.len(),
+                &Limits::new(),
+                1
+            ),
+            Ok(true)
+        );
+        assert_eq!(cursor, string.len());
+        let string = "%?\r\n+a\r\n:1\r\n.\r\n";
+        assert_eq!(
+            while_valid(
+                0x25,
+                string.as_bytes(),
+                &mut 0,
+                &string.len(),
+                &Limits::new(),
+                1
+            ),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn test_while_valid_streamed_invalid() {
+        let string = "%?\r\n+a\r\n.\r\n";
+        assert_eq!(
+            while_valid(
+                0x25,
+                string.as_bytes(),
+                &mut 0,
+                &string.len(),
+                &Limits::new(),
+                1
+            )
+            .unwrap_err(),
+            RespErrorKind::LengthsNotMatch
+        );
+        let string = ">?\r\n.\r\n";
+        assert_eq!(
+            while_valid(
+                0x3e,
+                string.as_bytes(),
+                &mut 0,
+                &string.len(),
+                &Limits::new(),
+                1
+            )
+            .unwrap_err(),
+            RespErrorKind::InvalidLength
+        );
+        let string = "*?\r\n:1\r\n";
+        assert_eq!(
+            while_valid(
+                0x2a,
+                string.as_bytes(),
+                &mut 0,
+                &string.len(),
+                &Limits::new(),
+                1
+            )
+            .unwrap_err(),
+            RespErrorKind::Incomplete { needed: None }
+        );
+    }
+
+    #[test]
+    fn test_while_valid_error_path() {
+        let string = "*3\r\n:1\r\n:2\r\n*2\r\n+OK\r\n:x\r\n";
+        let error = while_valid(
+            0x2a,
+            string.as_bytes(),
+            &mut 0,
+            &string.len(),
+            &Limits::new(),
+            1,
+        )
+        .unwrap_err();
+        assert_eq!(error, RespErrorKind::InvalidInteger);
+        assert_eq!(error.path(), &[2, 1]);
+        assert_eq!(error.offset(), 22);
+        assert_eq!(error.byte(), Some(b'x'));
+        assert_eq!(error.expected(), Some("digit"));
+        assert_eq!(error.type_name(), Some("Integer"));
+        let string = "%?\r\n+a\r\n:1\r\n+b\r\n:1\n";
+        let error = while_valid(
+            0x25,
+            string.as_bytes(),
+            &mut 0,
+            &string.len(),
+            &Limits::new(),
+            1,
+        )
+        .unwrap_err();
+        assert_eq!(error, RespErrorKind::InvalidTerminate);
+        assert_eq!(error.path(), &[3]);
+        assert_eq!(error.type_name(), Some("Integer"));
+    }
+
+    #[test]
+    fn test_while_valid_beyond_inline_depth() {
+        let string = format!("{}:1\r\n{}", "*2\r\n".repeat(20), ":2\r\n".repeat(20));
+        let mut cursor = 0;
+        assert_eq!(
+            while_valid(
+                0x2a,
+                string.as_bytes(),
+                &mut cursor,
+                &string.len(),
+                &Limits::new(),
+                1
+            ),
+            Ok(false)
+        );
+        assert_eq!(cursor, string.len());
+        let string = format!("{}:x\r\n", "*1\r\n".repeat(20));
+        let error = while_valid(
+            0x2a,
+            string.as_bytes(),
+            &mut 0,
+            &string.len(),
+            &Limits::new(),
+            1,
+        )
+        .unwrap_err();
+        assert_eq!(error, RespErrorKind::InvalidInteger);
+        assert_eq!(error.path(), &[0; 20]);
+    }
+}
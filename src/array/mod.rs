@@ -1,11 +1,12 @@
-use crate::{BulkString, Error, Integer, RespError, RespType, SimpleString};
-use bytes::{Buf, BufMut, Bytes, BytesMut};
+use crate::{aggregate, streamed, Limits, RespError, RespErrorKind, RespType};
+use bytes::{BufMut, Bytes, BytesMut};
+use std::convert::TryFrom;
 
 pub const EMPTY_ARRAY: Array = Array(Bytes::from_static(b"*0\r\n"));
 pub const NULL_ARRAY: Array = Array(Bytes::from_static(b"*-1\r\n"));
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct Array(Bytes);
+pub struct Array(pub(crate) Bytes);
 
 impl Array {
     #[inline]
@@ -21,12 +22,85 @@ impl Array {
     #[inline]
     pub fn to_vec(&self) -> Vec<u8> {
         let length = self.0.len();
-        let mut vector = Vec::<u8>::with_capacity(length);
-        unsafe {
-            vector.set_len(length - 3);
-        }
-        self.bytes().copy_to_slice(vector.as_mut_slice());
-        vector
+        self.0[..length - 3].to_vec()
+    }
+
+    /// Iterate over the elements of the Array
+    ///
+    /// Elements are validated lazily and share the bytes of the Array.
+    ///
+    /// # Panics
+    ///
+    /// Panics, here or while iterating, if the Array was built by `from_bytes_unchecked`
+    /// from bytes which do not hold a valid Array.
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{Array, BulkString, RespType};
+    ///
+    /// let string = "*2\r\n$3\r\nfoo\r\n$3\r\nbar\r\n";
+    /// let array = Array::parse(string.as_bytes(), &mut 0, &string.len()).unwrap();
+    /// let mut iter = array.iter();
+    /// assert_eq!(iter.next(), Some(RespType::BulkString(BulkString::new(b"foo"))));
+    /// assert_eq!(iter.next(), Some(RespType::BulkString(BulkString::new(b"bar"))));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> ArrayIter {
+        ArrayIter::new(0x2a, self.0.clone())
+    }
+
+    /// Number of elements, 0 for a Null Array
+    ///
+    /// # Panics
+    ///
+    /// Panics as [`Array::iter`] does.
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{ArrayBuilder, Integer, RespType};
+    ///
+    /// let array = ArrayBuilder::new()
+    ///     .insert(RespType::Integer(Integer::new(1)))
+    ///     .insert(RespType::Integer(Integer::new(2)))
+    ///     .build();
+    /// assert_eq!(array.count(), 2);
+    /// ```
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.iter().len()
+    }
+
+    /// Element at `index`, or `None` if it is out of bounds
+    ///
+    /// # Panics
+    ///
+    /// Panics as [`Array::iter`] does.
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{ArrayBuilder, Integer, RespType};
+    ///
+    /// let array = ArrayBuilder::new()
+    ///     .insert(RespType::Integer(Integer::new(1)))
+    ///     .insert(RespType::Integer(Integer::new(2)))
+    ///     .build();
+    /// assert_eq!(array.get(1), Some(RespType::Integer(Integer::new(2))));
+    /// assert_eq!(array.get(2), None);
+    /// ```
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<RespType> {
+        self.iter().nth(index)
+    }
+
+    /// Elements of the Array, none for a Null Array
+    ///
+    /// # Panics
+    ///
+    /// Panics as [`Array::iter`] does.
+    #[inline]
+    pub fn into_vec(self) -> Vec<RespType> {
+        self.iter().collect()
     }
 
     #[inline]
@@ -40,102 +114,159 @@ impl Array {
     }
 
     #[inline]
-    pub fn from_bytes(input: Bytes) -> Self {
+    pub fn from_bytes_unchecked(input: Bytes) -> Self {
         Self(input)
     }
 
     #[inline]
-    pub fn from_slice(input: &[u8]) -> Self {
+    pub fn from_slice_unchecked(input: &[u8]) -> Self {
         let bytes = Bytes::copy_from_slice(input);
-        Self::from_bytes(bytes)
+        Self::from_bytes_unchecked(bytes)
     }
 
+    #[deprecated(since = "0.0.11", note = "use from_bytes_unchecked")]
+    #[inline]
+    pub fn from_bytes(input: Bytes) -> Self {
+        Self::from_bytes_unchecked(input)
+    }
+
+    #[deprecated(since = "0.0.11", note = "use from_slice_unchecked")]
+    #[inline]
+    pub fn from_slice(input: &[u8]) -> Self {
+        Self::from_slice_unchecked(input)
+    }
+
+    /// Build a new Array from `input`, checking that it holds exactly one valid value
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{Array, RespErrorKind};
+    /// use bytes::Bytes;
+    ///
+    /// assert!(Array::try_from_bytes(Bytes::from_static(b"*1\r\n:1\r\n")).is_ok());
+    /// assert_eq!(
+    ///     Array::try_from_bytes(Bytes::from_static(b"*1\r\n:1\r\n\r\n")).unwrap_err(),
+    ///     RespErrorKind::TrailingBytes
+    /// );
+    /// ```
+    pub fn try_from_bytes(input: Bytes) -> Result<Self, RespError> {
+        let mut index = 0;
+        let is_streamed =
+            Self::while_valid_streamed(&input, &mut index, &input.len(), &Limits::unlimited())?;
+        if index != input.len() {
+            return Err(RespError::new(
+                RespErrorKind::TrailingBytes,
+                &input,
+                &input.len(),
+                index,
+            ));
+        }
+        Ok(Self::from_bytes_unchecked(streamed::reassemble_bytes(
+            input,
+            is_streamed,
+        )))
+    }
+
+    #[inline]
+    pub fn try_from_slice(input: &[u8]) -> Result<Self, RespError> {
+        Self::try_from_bytes(Bytes::copy_from_slice(input))
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated by the global allocator for exactly `length` bytes,
+    /// as required by [`Vec::from_raw_parts`].
+    #[deprecated(
+        since = "0.0.11",
+        note = "use `try_from` with an owned `Vec<u8>`, `String`, `Box<[u8]>` or `BytesMut`"
+    )]
     #[inline]
     pub unsafe fn from_raw(ptr: *mut u8, length: usize) -> Self {
         let vector = Vec::from_raw_parts(ptr, length, length);
         let bytes = Bytes::from(vector);
-        Self::from_bytes(bytes)
+        Self::from_bytes_unchecked(bytes)
     }
 
     pub fn while_valid(input: &[u8], start: &mut usize, end: &usize) -> Result<(), RespError> {
-        let mut index = *start;
-        if index + 3 >= *end {
-            return Err(RespError::InvalidValue);
-        }
-        if input[index] != 0x2a {
-            return Err(RespError::InvalidFirstChar);
-        }
-        index += 1;
-        if input[index] == 0x2d {
-            if input[index + 1] != 0x31
-                || input[index + 2] != 0x0d
-                || index + 3 == *end
-                || input[index + 3] != 0x0a
-            {
-                return Err(RespError::InvalidNullValue);
-            }
-            *start = index + 4;
-            return Ok(());
-        }
-        if input[index] == 0x30 && input[index + 1] >= 0x30 && input[index + 1] <= 0x39 {
-            return Err(RespError::InvalidLength);
-        }
-        while index < *end && input[index] >= 0x30 && input[index] <= 0x39 {
-            index += 1;
-        }
-        if index + 1 >= *end || input[index] != 0x0d || input[index + 1] != 0x0a {
-            return Err(RespError::InvalidLengthSeparator);
-        }
-        let length = unsafe {
-            String::from_utf8_unchecked(input[*start + 1..index].to_vec())
-                .parse::<usize>()
-                .unwrap()
-        };
-        index += 2;
-        if length == 0 {
-            *start = index;
-            return Ok(());
-        }
-        if index >= *end {
-            return Err(RespError::InvalidValue);
-        }
-        let mut count = 0;
-        while count < length {
-            match input[index] {
-                0x2b => {
-                    SimpleString::while_valid(input, &mut index, end)?;
-                }
-                0x2d => {
-                    Error::while_valid(input, &mut index, end)?;
-                }
-                0x3a => {
-                    Integer::while_valid(input, &mut index, end)?;
-                }
-                0x24 => {
-                    BulkString::while_valid(input, &mut index, end)?;
-                }
-                0x2a => {
-                    Self::while_valid(input, &mut index, end)?;
-                }
-                _ => {
-                    return Err(RespError::InvalidValue);
-                }
-            }
-            count += 1;
-        }
-        *start = index;
+        Self::while_valid_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Validate an Array, whose nesting, element counts and nested values are bound by `limits`
+    pub fn while_valid_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<(), RespError> {
+        Self::while_valid_streamed(input, start, end, limits)?;
         Ok(())
     }
 
+    /// Validate an Array like [`Array::while_valid_with`], returning whether it holds
+    /// streamed values
+    pub(crate) fn while_valid_streamed(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<bool, RespError> {
+        limits.while_valid_frame(input, start, end, |input, start, end| {
+            aggregate::while_valid(0x2a, input, start, end, limits, 1)
+        })
+    }
+
     pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, RespError> {
+        Self::parse_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Parse an Array, whose nesting, element counts and nested values are bound by `limits`
+    pub fn parse_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        let is_streamed = Self::while_valid_streamed(input, &mut index, end, limits)?;
+        let value = Self::from_bytes_unchecked(streamed::copy(&input[*start..index], is_streamed));
+        *start = index;
+        Ok(value)
+    }
+
+    /// Parse an Array from `input`, sharing its buffer instead of copying it
+    ///
+    /// A streamed Array, or any streamed value within it, is reassembled into a new buffer instead.
+    pub fn parse_bytes(input: &Bytes, start: &mut usize) -> Result<Self, RespError> {
+        Self::parse_bytes_with(input, start, &Limits::unlimited())
+    }
+
+    /// Parse an Array from `input` like [`Array::parse_bytes`], whose nesting, element counts and
+    /// nested values are bound by `limits`
+    pub fn parse_bytes_with(
+        input: &Bytes,
+        start: &mut usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
         let mut index = *start;
-        Self::while_valid(input, &mut index, end)?;
-        let value = Self::from_slice(&input[*start..index]);
+        let is_streamed = Self::while_valid_streamed(input, &mut index, &input.len(), limits)?;
+        let value = Self::from_bytes_unchecked(streamed::reassemble_bytes(
+            input.slice(*start..index),
+            is_streamed,
+        ));
         *start = index;
         Ok(value)
     }
 }
 
+impl TryFrom<Bytes> for Array {
+    type Error = RespError;
+
+    fn try_from(input: Bytes) -> Result<Self, RespError> {
+        Self::try_from_bytes(input)
+    }
+}
+
+#[allow(clippy::needless_lifetimes, clippy::partialeq_ne_impl)]
 impl<'a> PartialEq<Array> for &'a Array {
     fn eq(&self, other: &Array) -> bool {
         self.0 == other.bytes()
@@ -145,6 +276,84 @@ impl<'a> PartialEq<Array> for &'a Array {
     }
 }
 
+impl IntoIterator for Array {
+    type Item = RespType;
+    type IntoIter = ArrayIter;
+
+    fn into_iter(self) -> ArrayIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for &Array {
+    type Item = RespType;
+    type IntoIter = ArrayIter;
+
+    fn into_iter(self) -> ArrayIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the elements of an Array
+///
+/// Panics when it reaches an invalid element of an Array built by `from_bytes_unchecked`.
+#[derive(Debug, Clone)]
+pub struct ArrayIter {
+    bytes: Bytes,
+    index: usize,
+    remaining: usize,
+}
+
+impl ArrayIter {
+    /// Iterate over the values of the validated aggregate `bytes`, keys and values alike for a Map
+    pub(crate) fn new(first_char: u8, bytes: Bytes) -> Self {
+        let mut index = 0;
+        let count = aggregate::while_valid_header(
+            first_char,
+            &bytes,
+            &mut index,
+            &bytes.len(),
+            &Limits::unlimited(),
+        )
+        .ok()
+        .flatten()
+        .expect("aggregate holds a valid header");
+        Self {
+            bytes,
+            index,
+            remaining: count,
+        }
+    }
+}
+
+impl Iterator for ArrayIter {
+    type Item = RespType;
+
+    fn next(&mut self) -> Option<RespType> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let start = self.index;
+        RespType::while_valid_with(
+            &self.bytes,
+            &mut self.index,
+            &self.bytes.len(),
+            &Limits::unlimited(),
+        )
+        .expect("Array holds valid elements");
+        self.remaining -= 1;
+        Some(RespType::from_bytes_unchecked(
+            self.bytes.slice(start..self.index),
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for ArrayIter {}
+
 pub struct ArrayBuilder {
     inner: Vec<RespType>,
 }
@@ -159,6 +368,7 @@ impl ArrayBuilder {
     /// let array_builder: ArrayBuilder = ArrayBuilder::new();
     /// let array: Array = array_builder.build();
     /// ```
+    #[allow(clippy::new_without_default)]
     #[inline]
     pub fn new() -> ArrayBuilder {
         ArrayBuilder {
@@ -217,7 +427,8 @@ impl ArrayBuilder {
 #[cfg(test)]
 mod tests_array {
     use crate::{
-        Array, ArrayBuilder, BulkString, Integer, RespType, SimpleString, EMPTY_ARRAY, NULL_ARRAY,
+        Array, ArrayBuilder, Boolean, BulkString, Double, Integer, Limits, Null, RespErrorKind,
+        RespType, SimpleString, EMPTY_ARRAY, NULL_ARRAY,
     };
     use bytes::Bytes;
 
@@ -310,4 +521,173 @@ mod tests_array {
         assert_eq!(array, referance_array);
         assert_eq!(cursor, 22);
     }
+
+    #[test]
+    fn test_while_valid_incomplete() {
+        let string = "*2\r\n$3\r\nfoo\r\n$3\r\nba";
+        assert_eq!(
+            Array::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: Some(3) }
+        );
+        let string = "*3\r\n$3\r\nba";
+        assert_eq!(
+            Array::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: None }
+        );
+        let string = "*2\r\n$3\r\nfoo\r\n";
+        assert_eq!(
+            Array::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: None }
+        );
+        let string = "*2";
+        assert_eq!(
+            Array::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: None }
+        );
+        let string = "*-1\r";
+        assert_eq!(
+            Array::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: Some(1) }
+        );
+    }
+
+    #[test]
+    fn parse_array_with_resp3_scalars() {
+        let string = "*3\r\n_\r\n#f\r\n,-2.5\r\n";
+        let mut cursor = 0;
+        let array = Array::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+
+        let referance_array = ArrayBuilder::new()
+            .insert(RespType::Null(Null::new()))
+            .insert(RespType::Boolean(Boolean::new(false)))
+            .insert(RespType::Double(Double::new(-2.5)))
+            .build();
+
+        assert_eq!(array, referance_array);
+        assert_eq!(cursor, 18);
+    }
+
+    #[test]
+    fn test_while_valid_invalid_element() {
+        let string = "*2\r\n:1\r\n?\r\n";
+        assert_eq!(
+            Array::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::InvalidFirstChar
+        );
+    }
+
+    #[test]
+    fn test_parse_streamed() {
+        let string = "*?\r\n:1\r\n*?\r\n$?\r\n;3\r\nfoo\r\n;0\r\n.\r\n.\r\n";
+        let mut cursor = 0;
+        let array = Array::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+
+        let referance_array = ArrayBuilder::new()
+            .insert(RespType::Integer(Integer::new(1)))
+            .insert(RespType::Array(
+                ArrayBuilder::new()
+                    .insert(RespType::BulkString(BulkString::new(b"foo")))
+                    .build(),
+            ))
+            .build();
+
+        assert_eq!(array, referance_array);
+        assert_eq!(cursor, string.len());
+    }
+
+    #[test]
+    fn test_iter() {
+        let string = "*3\r\n:1\r\n*1\r\n+foo\r\n$3\r\nbar\r\n";
+        let array = Array::parse(string.as_bytes(), &mut 0, &string.len()).unwrap();
+        let elements: Vec<RespType> = array.iter().collect();
+        assert_eq!(
+            elements,
+            vec![
+                RespType::Integer(Integer::new(1)),
+                RespType::Array(
+                    ArrayBuilder::new()
+                        .insert(RespType::SimpleString(SimpleString::new(b"foo")))
+                        .build()
+                ),
+                RespType::BulkString(BulkString::new(b"bar")),
+            ]
+        );
+        assert_eq!(array.iter().len(), 3);
+        assert_eq!(array.count(), 3);
+        assert_eq!(
+            array.get(2),
+            Some(RespType::BulkString(BulkString::new(b"bar")))
+        );
+        assert_eq!(array.get(3), None);
+        assert_eq!(array.clone().into_vec(), elements);
+        assert_eq!((&array).into_iter().count(), 3);
+    }
+
+    #[test]
+    fn test_iter_empty_and_null() {
+        assert_eq!(EMPTY_ARRAY.iter().next(), None);
+        assert_eq!(EMPTY_ARRAY.count(), 0);
+        assert_eq!(NULL_ARRAY.iter().next(), None);
+        assert_eq!(NULL_ARRAY.count(), 0);
+        assert_eq!(NULL_ARRAY.into_vec(), Vec::new());
+    }
+
+    #[test]
+    fn test_iter_is_zero_copy() {
+        let array = ArrayBuilder::new()
+            .insert(RespType::BulkString(BulkString::new(b"foo")))
+            .build();
+        let bytes = array.bytes();
+        let element = array.get(0).unwrap();
+        assert_eq!(element.bytes().as_ptr(), bytes[4..].as_ptr());
+    }
+
+    #[test]
+    #[should_panic(expected = "Array holds valid elements")]
+    fn test_iter_invalid_unchecked() {
+        let array = Array::from_bytes_unchecked(Bytes::from_static(b"*2\r\n:1\r\n"));
+        array.into_vec();
+    }
+
+    #[test]
+    fn test_try_from_bytes() {
+        use std::convert::TryFrom;
+
+        let array = Array::try_from(Bytes::from_static(b"*1\r\n:1\r\n")).unwrap();
+        assert_eq!(array.get(0), Some(RespType::Integer(Integer::new(1))));
+        assert_eq!(
+            Array::try_from(Bytes::from_static(b"*1\r\n:1\r\n:2\r\n")).unwrap_err(),
+            RespErrorKind::TrailingBytes
+        );
+        assert_eq!(
+            Array::try_from(Bytes::from_static(b"*2\r\n:1\r\n+a\n")).unwrap_err(),
+            RespErrorKind::InvalidTerminate
+        );
+    }
+
+    #[test]
+    fn test_while_valid_deep_nesting() {
+        let depth = 100_000;
+        let string = format!("{}:1\r\n", "*1\r\n".repeat(depth));
+        let mut cursor = 0;
+        assert_eq!(
+            Array::while_valid(string.as_bytes(), &mut cursor, &string.len()),
+            Ok(())
+        );
+        assert_eq!(cursor, string.len());
+        let error =
+            Array::while_valid_with(string.as_bytes(), &mut 0, &string.len(), &Limits::new())
+                .unwrap_err();
+        assert_eq!(error, RespErrorKind::MaxDepthExceeded);
+        let string = &string[..string.len() - 1];
+        let error = Array::while_valid_with(
+            string.as_bytes(),
+            &mut 0,
+            &string.len(),
+            &Limits::unlimited(),
+        )
+        .unwrap_err();
+        assert_eq!(error, RespErrorKind::Incomplete { needed: Some(1) });
+        assert!(error.path().is_empty());
+    }
 }
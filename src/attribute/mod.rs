@@ -0,0 +1,342 @@
+use crate::{aggregate, streamed, Limits, Map, RespError, RespErrorKind, RespType};
+use bytes::{BufMut, Bytes, BytesMut};
+use std::convert::TryFrom;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attribute(pub(crate) Bytes);
+
+/// Attribute type (RESP3)
+///
+/// Holds the attributes together with the reply they describe.
+impl Attribute {
+    #[allow(clippy::len_without_is_empty)]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    pub fn bytes(&self) -> Bytes {
+        self.0.clone()
+    }
+
+    #[inline]
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+
+    /// Attributes as a Map
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Attribute was built by `from_bytes_unchecked` from bytes which do not hold
+    /// a valid Attribute.
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{AttributeBuilder, Integer, MapBuilder, RespType, SimpleString};
+    ///
+    /// let key = RespType::SimpleString(SimpleString::new(b"ttl"));
+    /// let value = RespType::Integer(Integer::new(3600));
+    /// let attribute = AttributeBuilder::new()
+    ///     .insert(key.clone(), value.clone())
+    ///     .build(RespType::SimpleString(SimpleString::new(b"OK")));
+    /// assert_eq!(attribute.attributes(), MapBuilder::new().insert(key, value).build());
+    /// ```
+    pub fn attributes(&self) -> Map {
+        let reply_index = self.reply_index();
+        let mut bytes = BytesMut::with_capacity(reply_index);
+        bytes.put_u8(0x25); // "%"
+        bytes.put_slice(&self.0[1..reply_index]);
+        Map::from_bytes_unchecked(bytes.freeze())
+    }
+
+    /// Reply described by the attributes
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Attribute was built by `from_bytes_unchecked` from bytes which do not hold
+    /// a valid Attribute.
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{AttributeBuilder, Integer, RespType, SimpleString};
+    ///
+    /// let reply = RespType::SimpleString(SimpleString::new(b"OK"));
+    /// let attribute = AttributeBuilder::new()
+    ///     .insert(
+    ///         RespType::SimpleString(SimpleString::new(b"ttl")),
+    ///         RespType::Integer(Integer::new(3600)),
+    ///     )
+    ///     .build(reply.clone());
+    /// assert_eq!(attribute.reply(), reply);
+    /// ```
+    pub fn reply(&self) -> RespType {
+        RespType::from_bytes_unchecked(self.0.slice(self.reply_index()..))
+    }
+
+    fn reply_index(&self) -> usize {
+        let input = &self.0[..];
+        let mut index = 0;
+        let count = aggregate::while_valid_header(
+            0x7c,
+            input,
+            &mut index,
+            &input.len(),
+            &Limits::unlimited(),
+        )
+        .ok()
+        .flatten()
+        .expect("Attribute holds a valid header");
+        for _ in 1..count {
+            RespType::while_valid_with(input, &mut index, &input.len(), &Limits::unlimited())
+                .expect("Attribute holds valid values");
+        }
+        index
+    }
+
+    #[inline]
+    pub fn from_bytes_unchecked(input: Bytes) -> Self {
+        Self(input)
+    }
+
+    #[inline]
+    pub fn from_slice_unchecked(input: &[u8]) -> Self {
+        let bytes = Bytes::copy_from_slice(input);
+        Self::from_bytes_unchecked(bytes)
+    }
+
+    /// Build a new Attribute from `input`, checking that it holds exactly one valid value
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{Attribute, RespErrorKind};
+    /// use bytes::Bytes;
+    ///
+    /// assert!(Attribute::try_from_bytes(Bytes::from_static(b"|1\r\n+a\r\n:1\r\n:2\r\n")).is_ok());
+    /// assert_eq!(
+    ///     Attribute::try_from_bytes(Bytes::from_static(b"|1\r\n+a\r\n:1\r\n:2\r\n\r\n")).unwrap_err(),
+    ///     RespErrorKind::TrailingBytes
+    /// );
+    /// ```
+    pub fn try_from_bytes(input: Bytes) -> Result<Self, RespError> {
+        let mut index = 0;
+        let is_streamed =
+            Self::while_valid_streamed(&input, &mut index, &input.len(), &Limits::unlimited())?;
+        if index != input.len() {
+            return Err(RespError::new(
+                RespErrorKind::TrailingBytes,
+                &input,
+                &input.len(),
+                index,
+            ));
+        }
+        Ok(Self::from_bytes_unchecked(streamed::reassemble_bytes(
+            input,
+            is_streamed,
+        )))
+    }
+
+    #[inline]
+    pub fn try_from_slice(input: &[u8]) -> Result<Self, RespError> {
+        Self::try_from_bytes(Bytes::copy_from_slice(input))
+    }
+
+    pub fn while_valid(input: &[u8], start: &mut usize, end: &usize) -> Result<(), RespError> {
+        Self::while_valid_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Validate an Attribute, whose entries and the reply it describes are bound by `limits`
+    pub fn while_valid_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<(), RespError> {
+        Self::while_valid_streamed(input, start, end, limits)?;
+        Ok(())
+    }
+
+    /// Validate an Attribute like [`Attribute::while_valid_with`], returning whether it holds
+    /// streamed values
+    pub(crate) fn while_valid_streamed(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<bool, RespError> {
+        limits.while_valid_frame(input, start, end, |input, start, end| {
+            aggregate::while_valid(0x7c, input, start, end, limits, 1)
+        })
+    }
+
+    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, RespError> {
+        Self::parse_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Parse an Attribute, whose entries and the reply it describes are bound by `limits`
+    pub fn parse_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        let is_streamed = Self::while_valid_streamed(input, &mut index, end, limits)?;
+        let value = Self::from_bytes_unchecked(streamed::copy(&input[*start..index], is_streamed));
+        *start = index;
+        Ok(value)
+    }
+
+    /// Parse an Attribute from `input`, sharing its buffer instead of copying it
+    ///
+    /// Streamed values within it are reassembled into a new buffer instead.
+    pub fn parse_bytes(input: &Bytes, start: &mut usize) -> Result<Self, RespError> {
+        Self::parse_bytes_with(input, start, &Limits::unlimited())
+    }
+
+    /// Parse an Attribute from `input` like [`Attribute::parse_bytes`], whose entries and the reply
+    /// it describes are bound by `limits`
+    pub fn parse_bytes_with(
+        input: &Bytes,
+        start: &mut usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        let is_streamed = Self::while_valid_streamed(input, &mut index, &input.len(), limits)?;
+        let value = Self::from_bytes_unchecked(streamed::reassemble_bytes(
+            input.slice(*start..index),
+            is_streamed,
+        ));
+        *start = index;
+        Ok(value)
+    }
+}
+
+impl TryFrom<Bytes> for Attribute {
+    type Error = RespError;
+
+    fn try_from(input: Bytes) -> Result<Self, RespError> {
+        Self::try_from_bytes(input)
+    }
+}
+
+impl PartialEq<Attribute> for &Attribute {
+    fn eq(&self, other: &Attribute) -> bool {
+        self.0 == other.bytes()
+    }
+}
+
+pub struct AttributeBuilder {
+    inner: Vec<(RespType, RespType)>,
+}
+
+impl Default for AttributeBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AttributeBuilder {
+    /// Build a new Attribute Builder
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{Attribute, AttributeBuilder, RespType, SimpleString};
+    ///
+    /// let attribute_builder: AttributeBuilder = AttributeBuilder::new();
+    /// let attribute: Attribute = attribute_builder.build(RespType::SimpleString(SimpleString::new(b"OK")));
+    /// ```
+    #[inline]
+    pub fn new() -> AttributeBuilder {
+        AttributeBuilder {
+            inner: Vec::<(RespType, RespType)>::new(),
+        }
+    }
+
+    #[inline]
+    pub fn value(&mut self) -> Vec<(RespType, RespType)> {
+        self.inner.clone()
+    }
+
+    /// Add a new attribute to Attribute Builder
+    #[inline]
+    pub fn insert(&mut self, key: RespType, value: RespType) -> &mut Self {
+        self.inner.push((key, value));
+        self
+    }
+
+    /// Build an Attribute prefixing `reply`
+    #[inline]
+    pub fn build(&self, reply: RespType) -> Attribute {
+        let length = self.inner.len();
+        let length_string = length.to_string();
+        let mut total_bytes = length_string.len() + 3 + reply.len();
+        for (key, value) in &self.inner {
+            total_bytes += key.len() + value.len();
+        }
+        let mut bytes = BytesMut::with_capacity(total_bytes);
+        bytes.put_u8(0x7c); // "|"
+        bytes.put_slice(length_string.as_bytes());
+        bytes.put_u8(0x0d); // CR
+        bytes.put_u8(0x0a); // LF
+        for (key, value) in &self.inner {
+            bytes.put(key.bytes());
+            bytes.put(value.bytes());
+        }
+        bytes.put(reply.bytes());
+        Attribute(bytes.freeze())
+    }
+}
+
+#[cfg(test)]
+mod tests_attribute {
+    use crate::{
+        Attribute, AttributeBuilder, Integer, MapBuilder, RespErrorKind, RespType, SimpleString,
+    };
+    use bytes::Bytes;
+
+    #[test]
+    fn test_build_attribute() {
+        let attribute = AttributeBuilder::new()
+            .insert(
+                RespType::SimpleString(SimpleString::new(b"ttl")),
+                RespType::Integer(Integer::new(3600)),
+            )
+            .build(RespType::Integer(Integer::new(1)));
+        assert_eq!(
+            attribute.bytes(),
+            Bytes::from_static(b"|1\r\n+ttl\r\n:3600\r\n:1\r\n")
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let string = "|1\r\n+ttl\r\n:3600\r\n*2\r\n:1\r\n:2\r\n+bar\r\n";
+        let mut cursor = 0;
+        let attribute = Attribute::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        assert_eq!(cursor, 29);
+        assert_eq!(
+            attribute.attributes(),
+            MapBuilder::new()
+                .insert(
+                    RespType::SimpleString(SimpleString::new(b"ttl")),
+                    RespType::Integer(Integer::new(3600)),
+                )
+                .build()
+        );
+        assert_eq!(
+            attribute.reply().bytes(),
+            Bytes::from_static(b"*2\r\n:1\r\n:2\r\n")
+        );
+    }
+
+    #[test]
+    fn test_while_valid_missing_reply() {
+        let string = "|1\r\n+ttl\r\n:3600\r\n";
+        assert_eq!(
+            Attribute::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: None }
+        );
+    }
+}
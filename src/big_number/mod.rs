@@ -0,0 +1,283 @@
+use crate::{Limits, RespError, RespErrorKind};
+use bytes::{BufMut, Bytes, BytesMut};
+use std::convert::TryFrom;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BigNumber(pub(crate) Bytes);
+
+/// Big number type (RESP3)
+impl BigNumber {
+    /// Build a new Big number from its decimal digits
+    ///
+    /// # Example
+    /// ```
+    /// use resp_protocol::BigNumber;
+    ///
+    /// let big_number = BigNumber::new(b"3492890328409238509324850943850943825024385");
+    /// ```
+    #[inline]
+    pub fn new(input: &[u8]) -> Self {
+        let mut bytes = BytesMut::with_capacity(input.len() + 3);
+        bytes.put_u8(0x28); // "("
+        bytes.put_slice(input);
+        bytes.put_u8(0x0d); // CR
+        bytes.put_u8(0x0a); // LF
+        Self::from_bytes_unchecked(bytes.freeze())
+    }
+
+    #[inline]
+    pub fn value(&self) -> Vec<u8> {
+        let length = self.0.len();
+        self.0.slice(1..(length - 2)).to_vec()
+    }
+
+    #[inline]
+    pub fn bytes(&self) -> Bytes {
+        self.0.clone()
+    }
+
+    #[allow(clippy::len_without_is_empty)]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Validate a value against `[-]<digits>`
+    pub fn validate_value(input: &[u8]) -> Result<(), RespError> {
+        let digits_start_index = match input.first() {
+            Some(0x2d) => 1,
+            _ => 0,
+        };
+        let invalid_index = match input[digits_start_index..]
+            .iter()
+            .position(|byte| !byte.is_ascii_digit())
+        {
+            Some(position) => Some(digits_start_index + position),
+            None if input.len() == digits_start_index => Some(digits_start_index),
+            None => None,
+        };
+        if let Some(index) = invalid_index {
+            return Err(
+                RespError::new(RespErrorKind::InvalidValue, input, &input.len(), index)
+                    .expecting("digit"),
+            );
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn from_bytes_unchecked(input: Bytes) -> Self {
+        Self(input)
+    }
+
+    #[inline]
+    pub fn from_slice_unchecked(input: &[u8]) -> Self {
+        let bytes = Bytes::copy_from_slice(input);
+        Self::from_bytes_unchecked(bytes)
+    }
+
+    /// Build a new BigNumber from `input`, checking that it holds exactly one valid value
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{BigNumber, RespErrorKind};
+    /// use bytes::Bytes;
+    ///
+    /// assert!(BigNumber::try_from_bytes(Bytes::from_static(b"(123\r\n")).is_ok());
+    /// assert_eq!(
+    ///     BigNumber::try_from_bytes(Bytes::from_static(b"(123\r\n\r\n")).unwrap_err(),
+    ///     RespErrorKind::TrailingBytes
+    /// );
+    /// ```
+    pub fn try_from_bytes(input: Bytes) -> Result<Self, RespError> {
+        let mut index = 0;
+        Self::while_valid(&input, &mut index, &input.len())?;
+        if index != input.len() {
+            return Err(RespError::new(
+                RespErrorKind::TrailingBytes,
+                &input,
+                &input.len(),
+                index,
+            ));
+        }
+        Ok(Self::from_bytes_unchecked(input))
+    }
+
+    #[inline]
+    pub fn try_from_slice(input: &[u8]) -> Result<Self, RespError> {
+        Self::try_from_bytes(Bytes::copy_from_slice(input))
+    }
+
+    pub fn while_valid(input: &[u8], start: &mut usize, end: &usize) -> Result<(), RespError> {
+        Self::while_valid_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Validate a Big Number, whose digits may not be longer than [`Limits::max_inline_length`]
+    pub fn while_valid_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<(), RespError> {
+        limits.while_valid_frame(input, start, end, |input, start, end| {
+            Self::while_valid_nested(input, start, end, limits)
+        })
+    }
+
+    pub(crate) fn while_valid_nested(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<(), RespError> {
+        let mut index = *start;
+        if index >= *end {
+            return Err(RespError::incomplete(None, input, end).within(0x28));
+        }
+        if input[index] != 0x28 {
+            return Err(
+                RespError::new(RespErrorKind::InvalidFirstChar, input, end, index)
+                    .expecting("(")
+                    .within(0x28),
+            );
+        }
+        index += 1;
+        while index < *end && input[index] != 0x0d && input[index] != 0x0a {
+            index += 1;
+        }
+        limits
+            .check_inline_length(*start + 1, index, input, end)
+            .map_err(|error| error.within(0x28))?;
+        if index >= *end {
+            return Err(RespError::incomplete(None, input, end).within(0x28));
+        }
+        if input[index] != 0x0d {
+            return Err(
+                RespError::new(RespErrorKind::InvalidTerminate, input, end, index)
+                    .expecting("\r")
+                    .within(0x28),
+            );
+        }
+        Self::validate_value(&input[*start + 1..index])
+            .map_err(|error| error.rebase(input, end, *start + 1).within(0x28))?;
+        if index + 1 >= *end {
+            return Err(RespError::incomplete(Some(1), input, end).within(0x28));
+        }
+        if input[index + 1] != 0x0a {
+            return Err(
+                RespError::new(RespErrorKind::InvalidTerminate, input, end, index + 1)
+                    .expecting("\n")
+                    .within(0x28),
+            );
+        }
+        *start = index + 2;
+        Ok(())
+    }
+
+    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, RespError> {
+        Self::parse_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Parse a Big Number, whose digits may not be longer than [`Limits::max_inline_length`]
+    pub fn parse_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        Self::while_valid_with(input, &mut index, end, limits)?;
+        let value = Self::from_slice_unchecked(&input[*start..index]);
+        *start = index;
+        Ok(value)
+    }
+
+    /// Parse a Big Number from `input`, sharing its buffer instead of copying it
+    pub fn parse_bytes(input: &Bytes, start: &mut usize) -> Result<Self, RespError> {
+        Self::parse_bytes_with(input, start, &Limits::unlimited())
+    }
+
+    /// Parse a Big Number from `input` like [`BigNumber::parse_bytes`], whose digits may not be
+    /// longer than [`Limits::max_inline_length`]
+    pub fn parse_bytes_with(
+        input: &Bytes,
+        start: &mut usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        Self::while_valid_with(input, &mut index, &input.len(), limits)?;
+        let value = Self::from_bytes_unchecked(input.slice(*start..index));
+        *start = index;
+        Ok(value)
+    }
+}
+
+impl TryFrom<Bytes> for BigNumber {
+    type Error = RespError;
+
+    fn try_from(input: Bytes) -> Result<Self, RespError> {
+        Self::try_from_bytes(input)
+    }
+}
+
+#[cfg(test)]
+mod tests_big_number {
+    use crate::big_number::BigNumber;
+    use crate::RespErrorKind;
+    use bytes::Bytes;
+
+    #[test]
+    fn test_new() {
+        let big_number = BigNumber::new(b"3492890328409238509324850943850943825024385");
+        assert_eq!(
+            big_number,
+            BigNumber(Bytes::from_static(
+                b"(3492890328409238509324850943850943825024385\r\n"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_value() {
+        let big_number = BigNumber(Bytes::from_static(b"(-12345678901234567890\r\n"));
+        assert_eq!(big_number.value(), Vec::from("-12345678901234567890"));
+    }
+
+    #[test]
+    fn test_validate_valid_value() {
+        assert_eq!(BigNumber::validate_value(b"-12345678901234567890"), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_invalid_value() {
+        for value in ["", "-", "12a", "1.5", "+1"] {
+            assert_eq!(
+                BigNumber::validate_value(value.as_bytes()).unwrap_err(),
+                RespErrorKind::InvalidValue,
+                "{}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let string = "(12345678901234567890\r\n+bar\r\n";
+        let mut cursor = 0;
+        let end = string.len();
+        assert_eq!(
+            BigNumber::parse(string.as_bytes(), &mut cursor, &end).unwrap(),
+            BigNumber::new(b"12345678901234567890")
+        );
+        assert_eq!(cursor, 23);
+    }
+
+    #[test]
+    fn test_while_valid_invalid_value() {
+        let string = "(12x\r\n";
+        assert_eq!(
+            BigNumber::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::InvalidValue
+        );
+    }
+}
@@ -0,0 +1,298 @@
+use crate::{length, Limits, RespError, RespErrorKind};
+
+/// Validate a length-prefixed blob, `<type><length>\r\n<value>\r\n`, and return whether it was streamed
+///
+/// Only a Bulk String may be null, or streamed as `$?\r\n` followed by chunks.
+pub(crate) fn while_valid(
+    first_char: u8,
+    input: &[u8],
+    start: &mut usize,
+    end: &usize,
+    limits: &Limits,
+) -> Result<bool, RespError> {
+    let mut index = *start;
+    if index >= *end {
+        return Err(RespError::incomplete(None, input, end).within(first_char));
+    }
+    if input[index] != first_char {
+        return Err(
+            RespError::new(RespErrorKind::InvalidFirstChar, input, end, index).within(first_char),
+        );
+    }
+    index += 1;
+    if index >= *end {
+        return Err(RespError::incomplete(None, input, end).within(first_char));
+    }
+    if first_char == 0x24 && input[index] == 0x2d {
+        length::while_valid_token(
+            b"-1\r\n",
+            RespErrorKind::InvalidNullValue,
+            input,
+            &mut index,
+            end,
+        )
+        .map_err(|error| error.within(first_char))?;
+        *start = index;
+        return Ok(false);
+    }
+    if first_char == 0x24 && input[index] == 0x3f {
+        // More chunks may follow an incomplete header or chunk, so the bytes missing are unknown.
+        length::while_valid_token(
+            b"?\r\n",
+            RespErrorKind::InvalidLength,
+            input,
+            &mut index,
+            end,
+        )
+        .map_err(|error| error.followed_by_values().within(first_char))?;
+        while while_valid_chunk(input, &mut index, end, limits)
+            .map_err(RespError::followed_by_values)?
+            > 0
+        {}
+        *start = index;
+        return Ok(true);
+    }
+    let length_start_index = index;
+    let length =
+        length::while_valid(input, &mut index, end).map_err(|error| error.within(first_char))?;
+    limits
+        .check_bulk_length(length, input, end, length_start_index)
+        .map_err(|error| error.within(first_char))?;
+    while_valid_value(length, input, &mut index, end).map_err(|error| error.within(first_char))?;
+    *start = index;
+    Ok(false)
+}
+
+/// Validate the header of a streamed string, `$?\r\n`
+pub(crate) fn while_valid_streamed_header(
+    input: &[u8],
+    start: &mut usize,
+    end: &usize,
+) -> Result<(), RespError> {
+    let mut index = *start;
+    if index >= *end {
+        return Err(RespError::incomplete(None, input, end).within(0x24));
+    }
+    if input[index] != 0x24 {
+        return Err(
+            RespError::new(RespErrorKind::InvalidFirstChar, input, end, index)
+                .expecting("$")
+                .within(0x24),
+        );
+    }
+    index += 1;
+    length::while_valid_token(
+        b"?\r\n",
+        RespErrorKind::InvalidLength,
+        input,
+        &mut index,
+        end,
+    )
+    .map_err(|error| error.within(0x24))?;
+    *start = index;
+    Ok(())
+}
+
+/// Validate a chunk of a streamed string, `;<length>\r\n<value>\r\n`, and return its length
+///
+/// The last chunk, `;0\r\n`, has no value.
+pub(crate) fn while_valid_chunk(
+    input: &[u8],
+    start: &mut usize,
+    end: &usize,
+    limits: &Limits,
+) -> Result<usize, RespError> {
+    let mut index = *start;
+    if index >= *end {
+        return Err(RespError::incomplete(None, input, end).within(0x24));
+    }
+    if input[index] != 0x3b {
+        return Err(
+            RespError::new(RespErrorKind::InvalidFirstChar, input, end, index)
+                .expecting(";")
+                .within(0x24),
+        );
+    }
+    index += 1;
+    let length_start_index = index;
+    let length = length::while_valid(input, &mut index, end).map_err(|error| error.within(0x24))?;
+    limits
+        .check_bulk_length(length, input, end, length_start_index)
+        .map_err(|error| error.within(0x24))?;
+    if length == 0 {
+        length::while_valid_token(
+            b"\r\n",
+            RespErrorKind::InvalidLengthSeparator,
+            input,
+            &mut index,
+            end,
+        )
+        .map_err(|error| error.within(0x24))?;
+    } else {
+        while_valid_value(length, input, &mut index, end).map_err(|error| error.within(0x24))?;
+    }
+    *start = index;
+    Ok(length)
+}
+
+/// Validate `\r\n<value>\r\n` of the declared length, starting on the CR ending the length
+fn while_valid_value(
+    length: usize,
+    input: &[u8],
+    start: &mut usize,
+    end: &usize,
+) -> Result<(), RespError> {
+    let index = *start;
+    let value_end_index = (index + 2)
+        .checked_add(length)
+        .and_then(|index| index.checked_add(2))
+        .ok_or_else(|| RespError::new(RespErrorKind::InvalidLength, input, end, index))?;
+    if index + 1 < *end && input[index + 1] != 0x0a {
+        return Err(
+            RespError::new(RespErrorKind::InvalidLengthSeparator, input, end, index + 1)
+                .expecting("\n"),
+        );
+    }
+    // Blobs are binary safe: trust the declared length and skip the value.
+    if value_end_index > *end {
+        return Err(RespError::incomplete(
+            Some(value_end_index - *end),
+            input,
+            end,
+        ));
+    }
+    if input[value_end_index - 2] != 0x0d {
+        return Err(RespError::new(
+            RespErrorKind::InvalidTerminate,
+            input,
+            end,
+            value_end_index - 2,
+        )
+        .expecting("\r\n"));
+    }
+    if input[value_end_index - 1] != 0x0a {
+        return Err(RespError::new(
+            RespErrorKind::InvalidTerminate,
+            input,
+            end,
+            value_end_index - 1,
+        )
+        .expecting("\n"));
+    }
+    *start = value_end_index;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests_blob {
+    use crate::blob::while_valid;
+    use crate::{Limits, RespErrorKind};
+
+    #[test]
+    fn test_while_valid() {
+        let string = "!3\r\nERR\r\n";
+        let mut cursor = 0;
+        assert_eq!(
+            while_valid(
+                0x21,
+                string.as_bytes(),
+                &mut cursor,
+                &string.len(),
+                &Limits::new()
+            ),
+            Ok(false)
+        );
+        assert_eq!(cursor, string.len());
+    }
+
+    #[test]
+    fn test_while_valid_null() {
+        let string = "$-1\r\n";
+        assert_eq!(
+            while_valid(
+                0x24,
+                string.as_bytes(),
+                &mut 0,
+                &string.len(),
+                &Limits::new()
+            ),
+            Ok(false)
+        );
+        let string = "!-1\r\n";
+        assert_eq!(
+            while_valid(
+                0x21,
+                string.as_bytes(),
+                &mut 0,
+                &string.len(),
+                &Limits::new()
+            )
+            .unwrap_err(),
+            RespErrorKind::InvalidLength
+        );
+    }
+
+    #[test]
+    fn test_while_valid_streamed() {
+        let string = "$?\r\n;4\r\nHell\r\n;5\r\no wor\r\n;1\r\nd\r\n;0\r\n+OK\r\n";
+        let mut cursor = 0;
+        assert_eq!(
+            while_valid(
+                0x24,
+                string.as_bytes(),
+                &mut cursor,
+                &string.len(),
+                &Limits::new()
+            ),
+            Ok(true)
+        );
+        assert_eq!(cursor, string.len() - 5);
+    }
+
+    #[test]
+    fn test_while_valid_streamed_incomplete() {
+        for string in ["$?", "$?\r\n", "$?\r\n;4\r\nHe", "$?\r\n;4\r\nHell\r\n;0\r"] {
+            assert_eq!(
+                while_valid(
+                    0x24,
+                    string.as_bytes(),
+                    &mut 0,
+                    &string.len(),
+                    &Limits::new()
+                )
+                .unwrap_err(),
+                RespErrorKind::Incomplete { needed: None },
+                "{:?}",
+                string
+            );
+        }
+    }
+
+    #[test]
+    fn test_while_valid_streamed_invalid() {
+        let string = "$?\r\n;4\r\nHell\r\n+OK\r\n";
+        assert_eq!(
+            while_valid(
+                0x24,
+                string.as_bytes(),
+                &mut 0,
+                &string.len(),
+                &Limits::new()
+            )
+            .unwrap_err(),
+            RespErrorKind::InvalidFirstChar
+        );
+        let string = "!?\r\n;0\r\n";
+        assert_eq!(
+            while_valid(
+                0x21,
+                string.as_bytes(),
+                &mut 0,
+                &string.len(),
+                &Limits::new()
+            )
+            .unwrap_err(),
+            RespErrorKind::InvalidLength
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use crate::{blob, Limits, RespError, RespErrorKind};
+use bytes::{BufMut, Bytes, BytesMut};
+use std::convert::TryFrom;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlobError(pub(crate) Bytes);
+
+/// Blob error type (RESP3)
+impl BlobError {
+    /// Build a new Blob error
+    ///
+    /// # Example
+    /// ```
+    /// use resp_protocol::BlobError;
+    ///
+    /// let blob_error = BlobError::new(b"SYNTAX invalid syntax");
+    /// println!("{:?}", blob_error); // BlobError(b"!21\r\nSYNTAX invalid syntax\r\n")
+    /// ```
+    pub fn new(input: &[u8]) -> Self {
+        let length_string = input.len().to_string();
+        let mut bytes = BytesMut::with_capacity(input.len() + length_string.len() + 5);
+        bytes.put_u8(0x21); // "!"
+        bytes.put_slice(length_string.as_bytes());
+        bytes.put_u8(0x0d); // CR
+        bytes.put_u8(0x0a); // LF
+        bytes.put_slice(input);
+        bytes.put_u8(0x0d); // CR
+        bytes.put_u8(0x0a); // LF
+        Self::from_bytes_unchecked(bytes.freeze())
+    }
+
+    #[inline]
+    pub fn bytes(&self) -> Bytes {
+        self.0.clone()
+    }
+
+    #[allow(clippy::len_without_is_empty)]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    fn value_start_index(&self) -> usize {
+        let mut index = 1;
+        while self.0[index] != 0x0d {
+            index += 1;
+        }
+        index + 2
+    }
+
+    ///
+    ///
+    /// ``` rust
+    /// use resp_protocol::BlobError;
+    ///
+    /// let blob_error: BlobError = BlobError::new(b"SYNTAX invalid syntax");
+    /// assert_eq!(blob_error.value(), "SYNTAX invalid syntax");
+    /// ```
+    #[inline]
+    pub fn value(&self) -> Bytes {
+        self.0.slice(self.value_start_index()..self.0.len() - 2)
+    }
+
+    #[inline]
+    pub fn value_len(&self) -> usize {
+        self.0.len() - 2 - self.value_start_index()
+    }
+
+    #[inline]
+    pub fn from_bytes_unchecked(input: Bytes) -> Self {
+        Self(input)
+    }
+
+    #[inline]
+    pub fn from_slice_unchecked(input: &[u8]) -> Self {
+        let bytes = Bytes::copy_from_slice(input);
+        Self::from_bytes_unchecked(bytes)
+    }
+
+    /// Build a new BlobError from `input`, checking that it holds exactly one valid value
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{BlobError, RespErrorKind};
+    /// use bytes::Bytes;
+    ///
+    /// assert!(BlobError::try_from_bytes(Bytes::from_static(b"!3\r\nERR\r\n")).is_ok());
+    /// assert_eq!(
+    ///     BlobError::try_from_bytes(Bytes::from_static(b"!3\r\nERR\r\n\r\n")).unwrap_err(),
+    ///     RespErrorKind::TrailingBytes
+    /// );
+    /// ```
+    pub fn try_from_bytes(input: Bytes) -> Result<Self, RespError> {
+        let mut index = 0;
+        Self::while_valid(&input, &mut index, &input.len())?;
+        if index != input.len() {
+            return Err(RespError::new(
+                RespErrorKind::TrailingBytes,
+                &input,
+                &input.len(),
+                index,
+            ));
+        }
+        Ok(Self::from_bytes_unchecked(input))
+    }
+
+    #[inline]
+    pub fn try_from_slice(input: &[u8]) -> Result<Self, RespError> {
+        Self::try_from_bytes(Bytes::copy_from_slice(input))
+    }
+
+    pub fn while_valid(input: &[u8], start: &mut usize, end: &usize) -> Result<(), RespError> {
+        Self::while_valid_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Validate a Blob Error declaring no more than [`Limits::max_bulk_length`] bytes
+    pub fn while_valid_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<(), RespError> {
+        limits.while_valid_frame(input, start, end, |input, start, end| {
+            Self::while_valid_nested(input, start, end, limits)
+        })
+    }
+
+    pub(crate) fn while_valid_nested(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<(), RespError> {
+        blob::while_valid(0x21, input, start, end, limits)?;
+        Ok(())
+    }
+
+    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, RespError> {
+        Self::parse_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Parse a Blob Error declaring no more than [`Limits::max_bulk_length`] bytes
+    pub fn parse_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        Self::while_valid_with(input, &mut index, end, limits)?;
+        let value = Self::from_slice_unchecked(&input[*start..index]);
+        *start = index;
+        Ok(value)
+    }
+
+    /// Parse a Blob Error from `input`, sharing its buffer instead of copying it
+    pub fn parse_bytes(input: &Bytes, start: &mut usize) -> Result<Self, RespError> {
+        Self::parse_bytes_with(input, start, &Limits::unlimited())
+    }
+
+    /// Parse a Blob Error from `input` like [`BlobError::parse_bytes`], declaring no more than
+    /// [`Limits::max_bulk_length`] bytes
+    pub fn parse_bytes_with(
+        input: &Bytes,
+        start: &mut usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        Self::while_valid_with(input, &mut index, &input.len(), limits)?;
+        let value = Self::from_bytes_unchecked(input.slice(*start..index));
+        *start = index;
+        Ok(value)
+    }
+}
+
+impl TryFrom<Bytes> for BlobError {
+    type Error = RespError;
+
+    fn try_from(input: Bytes) -> Result<Self, RespError> {
+        Self::try_from_bytes(input)
+    }
+}
+
+#[cfg(test)]
+mod tests_blob_error {
+    use crate::blob_error::BlobError;
+    use crate::RespErrorKind;
+    use bytes::Bytes;
+
+    #[test]
+    fn test_new() {
+        let blob_error = BlobError::new(b"SYNTAX invalid syntax");
+        assert_eq!(
+            blob_error,
+            BlobError(Bytes::from_static(b"!21\r\nSYNTAX invalid syntax\r\n"))
+        );
+    }
+
+    #[test]
+    fn test_value() {
+        let blob_error = BlobError(Bytes::from_static(b"!10\r\nERR\r\nfoo\r\n\r\n"));
+        assert_eq!(blob_error.value(), Bytes::from_static(b"ERR\r\nfoo\r\n"));
+        assert_eq!(blob_error.value_len(), 10);
+    }
+
+    #[test]
+    fn test_parse() {
+        let string = "!3\r\nERR\r\n+bar\r\n";
+        let mut cursor = 0;
+        assert_eq!(
+            BlobError::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap(),
+            BlobError::new(b"ERR")
+        );
+        assert_eq!(cursor, 9);
+    }
+
+    #[test]
+    fn test_while_valid_incomplete() {
+        let string = "!21\r\nSYNTAX";
+        assert_eq!(
+            BlobError::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: Some(17) }
+        );
+    }
+}
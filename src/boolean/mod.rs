@@ -0,0 +1,268 @@
+use crate::{Limits, RespError, RespErrorKind};
+use bytes::Bytes;
+use std::convert::TryFrom;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Boolean(pub(crate) Bytes);
+
+/// Boolean type (RESP3)
+impl Boolean {
+    /// Build a new Boolean
+    ///
+    /// # Example
+    /// ```
+    /// use resp_protocol::Boolean;
+    ///
+    /// let boolean = Boolean::new(true);
+    /// println!("{:?}", boolean); // Boolean(b"#t\r\n")
+    /// ```
+    #[inline]
+    pub fn new(input: bool) -> Self {
+        if input {
+            Self::from_bytes_unchecked(Bytes::from_static(b"#t\r\n"))
+        } else {
+            Self::from_bytes_unchecked(Bytes::from_static(b"#f\r\n"))
+        }
+    }
+
+    #[inline]
+    pub fn value(&self) -> bool {
+        self.0[1] == 0x74
+    }
+
+    #[inline]
+    pub fn bytes(&self) -> Bytes {
+        self.0.clone()
+    }
+
+    #[allow(clippy::len_without_is_empty)]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn validate_value(input: &[u8]) -> Result<(), RespError> {
+        if input != b"t" && input != b"f" {
+            return Err(
+                RespError::new(RespErrorKind::InvalidValue, input, &input.len(), 0)
+                    .expecting("t or f"),
+            );
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn from_bytes_unchecked(input: Bytes) -> Self {
+        Self(input)
+    }
+
+    #[inline]
+    pub fn from_slice_unchecked(input: &[u8]) -> Self {
+        let bytes = Bytes::copy_from_slice(input);
+        Self::from_bytes_unchecked(bytes)
+    }
+
+    /// Build a new Boolean from `input`, checking that it holds exactly one valid value
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{Boolean, RespErrorKind};
+    /// use bytes::Bytes;
+    ///
+    /// assert!(Boolean::try_from_bytes(Bytes::from_static(b"#t\r\n")).is_ok());
+    /// assert_eq!(
+    ///     Boolean::try_from_bytes(Bytes::from_static(b"#t\r\n\r\n")).unwrap_err(),
+    ///     RespErrorKind::TrailingBytes
+    /// );
+    /// ```
+    pub fn try_from_bytes(input: Bytes) -> Result<Self, RespError> {
+        let mut index = 0;
+        Self::while_valid(&input, &mut index, &input.len())?;
+        if index != input.len() {
+            return Err(RespError::new(
+                RespErrorKind::TrailingBytes,
+                &input,
+                &input.len(),
+                index,
+            ));
+        }
+        Ok(Self::from_bytes_unchecked(input))
+    }
+
+    #[inline]
+    pub fn try_from_slice(input: &[u8]) -> Result<Self, RespError> {
+        Self::try_from_bytes(Bytes::copy_from_slice(input))
+    }
+
+    pub fn while_valid(input: &[u8], start: &mut usize, end: &usize) -> Result<(), RespError> {
+        Self::while_valid_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Validate a Boolean, rejecting input longer than [`Limits::max_frame_size`]
+    pub fn while_valid_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<(), RespError> {
+        limits.while_valid_frame(input, start, end, |input, start, end| {
+            Self::while_valid_nested(input, start, end, limits)
+        })
+    }
+
+    pub(crate) fn while_valid_nested(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        _limits: &Limits,
+    ) -> Result<(), RespError> {
+        let mut index = *start;
+        if index >= *end {
+            return Err(RespError::incomplete(None, input, end).within(0x23));
+        }
+        if input[index] != 0x23 {
+            return Err(
+                RespError::new(RespErrorKind::InvalidFirstChar, input, end, index)
+                    .expecting("#")
+                    .within(0x23),
+            );
+        }
+        index += 1;
+        if index >= *end {
+            return Err(RespError::incomplete(Some(3), input, end).within(0x23));
+        }
+        if input[index] != 0x74 && input[index] != 0x66 {
+            return Err(
+                RespError::new(RespErrorKind::InvalidValue, input, end, index)
+                    .expecting("t or f")
+                    .within(0x23),
+            );
+        }
+        index += 1;
+        if index >= *end {
+            return Err(RespError::incomplete(Some(2), input, end).within(0x23));
+        }
+        if input[index] != 0x0d {
+            return Err(
+                RespError::new(RespErrorKind::InvalidTerminate, input, end, index)
+                    .expecting("\r")
+                    .within(0x23),
+            );
+        }
+        if index + 1 >= *end {
+            return Err(RespError::incomplete(Some(1), input, end).within(0x23));
+        }
+        if input[index + 1] != 0x0a {
+            return Err(
+                RespError::new(RespErrorKind::InvalidTerminate, input, end, index + 1)
+                    .expecting("\n")
+                    .within(0x23),
+            );
+        }
+        *start = index + 2;
+        Ok(())
+    }
+
+    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, RespError> {
+        Self::parse_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Parse a Boolean, rejecting input longer than [`Limits::max_frame_size`]
+    pub fn parse_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        Self::while_valid_with(input, &mut index, end, limits)?;
+        let value = Self::from_slice_unchecked(&input[*start..index]);
+        *start = index;
+        Ok(value)
+    }
+
+    /// Parse a Boolean from `input`, sharing its buffer instead of copying it
+    pub fn parse_bytes(input: &Bytes, start: &mut usize) -> Result<Self, RespError> {
+        Self::parse_bytes_with(input, start, &Limits::unlimited())
+    }
+
+    /// Parse a Boolean from `input` like [`Boolean::parse_bytes`], rejecting input longer than
+    /// [`Limits::max_frame_size`]
+    pub fn parse_bytes_with(
+        input: &Bytes,
+        start: &mut usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        Self::while_valid_with(input, &mut index, &input.len(), limits)?;
+        let value = Self::from_bytes_unchecked(input.slice(*start..index));
+        *start = index;
+        Ok(value)
+    }
+}
+
+impl TryFrom<Bytes> for Boolean {
+    type Error = RespError;
+
+    fn try_from(input: Bytes) -> Result<Self, RespError> {
+        Self::try_from_bytes(input)
+    }
+}
+
+#[cfg(test)]
+mod tests_boolean {
+    use crate::boolean::Boolean;
+    use crate::RespErrorKind;
+    use bytes::Bytes;
+
+    #[test]
+    fn test_new() {
+        assert_eq!(Boolean::new(true), Boolean(Bytes::from_static(b"#t\r\n")));
+        assert_eq!(Boolean::new(false), Boolean(Bytes::from_static(b"#f\r\n")));
+    }
+
+    #[test]
+    fn test_value() {
+        assert!(Boolean(Bytes::from_static(b"#t\r\n")).value());
+        assert!(!Boolean(Bytes::from_static(b"#f\r\n")).value());
+    }
+
+    #[test]
+    fn test_validate_value() {
+        assert_eq!(Boolean::validate_value(b"t"), Ok(()));
+        assert_eq!(
+            Boolean::validate_value(b"true").unwrap_err(),
+            RespErrorKind::InvalidValue
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let string = "#f\r\n+bar\r\n";
+        let mut cursor = 0;
+        let end = string.len();
+        assert_eq!(
+            Boolean::parse(string.as_bytes(), &mut cursor, &end).unwrap(),
+            Boolean::new(false)
+        );
+        assert_eq!(cursor, 4);
+    }
+
+    #[test]
+    fn test_while_valid_incomplete() {
+        let string = "#t";
+        assert_eq!(
+            Boolean::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: Some(2) }
+        );
+    }
+
+    #[test]
+    fn test_while_valid_invalid_value() {
+        let string = "#x\r\n";
+        assert_eq!(
+            Boolean::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::InvalidValue
+        );
+    }
+}
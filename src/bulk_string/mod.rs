@@ -1,11 +1,12 @@
-use crate::RespError;
+use crate::{blob, streamed, Limits, RespError, RespErrorKind};
 use bytes::{BufMut, Bytes, BytesMut};
+use std::convert::TryFrom;
 
 pub const EMPTY_BULK_STRING: BulkString = BulkString(Bytes::from_static(b"$0\r\n\r\n"));
 pub const NULL_BULK_STRING: BulkString = BulkString(Bytes::from_static(b"$-1\r\n"));
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct BulkString(Bytes);
+pub struct BulkString(pub(crate) Bytes);
 
 impl BulkString {
     /// Build a new Bulk String
@@ -30,7 +31,7 @@ impl BulkString {
         bytes.put_slice(input);
         bytes.put_u8(0x0d); // CR
         bytes.put_u8(0x0a); // LF
-        Self::from_bytes(bytes.freeze())
+        Self::from_bytes_unchecked(bytes.freeze())
     }
 
     ///
@@ -71,82 +72,250 @@ impl BulkString {
         self.0.len()
     }
 
+    ///
+    ///
+    /// ``` rust
+    /// use resp_protocol::{BulkString, NULL_BULK_STRING};
+    /// use bytes::Bytes;
+    ///
+    /// let bulk_string: BulkString = BulkString::new(b"foobar");
+    /// let value: Option<Bytes> = bulk_string.value();
+    /// println!("{:?}", value); // Some(b"foobar")
+    /// println!("{:?}", NULL_BULK_STRING.value()); // None
+    /// ```
     #[inline]
-    pub fn from_bytes(input: Bytes) -> Self {
+    pub fn value(&self) -> Option<Bytes> {
+        if self.is_null() {
+            return None;
+        }
+        Some(self.0.slice(self.value_start_index()..self.0.len() - 2))
+    }
+
+    /// Length of the value, 0 for a Null Bulk String
+    ///
+    /// ``` rust
+    /// use resp_protocol::BulkString;
+    ///
+    /// let bulk_string: BulkString = BulkString::new(b"foobar");
+    /// let value_length: usize = bulk_string.value_len();
+    /// println!("{:?}", value_length); // 6
+    /// ```
+    #[inline]
+    pub fn value_len(&self) -> usize {
+        if self.is_null() {
+            return 0;
+        }
+        self.0.len() - 2 - self.value_start_index()
+    }
+
+    /// Value as a string slice, or `None` if it is null or not valid UTF-8
+    ///
+    /// ``` rust
+    /// use resp_protocol::BulkString;
+    ///
+    /// let bulk_string: BulkString = BulkString::new(b"foobar");
+    /// println!("{:?}", bulk_string.as_str()); // Some("foobar")
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> Option<&str> {
+        if self.is_null() {
+            return None;
+        }
+        std::str::from_utf8(&self.0[self.value_start_index()..self.0.len() - 2]).ok()
+    }
+
+    ///
+    ///
+    /// ``` rust
+    /// use resp_protocol::BulkString;
+    ///
+    /// let bulk_string: BulkString = BulkString::new(b"OK");
+    /// let value: Option<Vec<u8>> = bulk_string.to_vec();
+    /// println!("{:?}", value); // Some([79, 75])
+    /// ```
+    #[inline]
+    pub fn to_vec(&self) -> Option<Vec<u8>> {
+        if self.is_null() {
+            return None;
+        }
+        Some(self.0[self.value_start_index()..self.0.len() - 2].to_vec())
+    }
+
+    #[inline]
+    fn value_start_index(&self) -> usize {
+        let mut index = 1;
+        while self.0[index] != 0x0d {
+            index += 1;
+        }
+        index + 2
+    }
+
+    #[inline]
+    pub fn from_bytes_unchecked(input: Bytes) -> Self {
         Self(input)
     }
 
     #[inline]
-    pub fn from_slice(input: &[u8]) -> Self {
+    pub fn from_slice_unchecked(input: &[u8]) -> Self {
         let bytes = Bytes::copy_from_slice(input);
-        Self::from_bytes(bytes)
+        Self::from_bytes_unchecked(bytes)
     }
 
+    #[deprecated(since = "0.0.11", note = "use from_bytes_unchecked")]
+    #[inline]
+    pub fn from_bytes(input: Bytes) -> Self {
+        Self::from_bytes_unchecked(input)
+    }
+
+    #[deprecated(since = "0.0.11", note = "use from_slice_unchecked")]
+    #[inline]
+    pub fn from_slice(input: &[u8]) -> Self {
+        Self::from_slice_unchecked(input)
+    }
+
+    /// Build a new BulkString from `input`, checking that it holds exactly one valid value
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{BulkString, RespErrorKind};
+    /// use bytes::Bytes;
+    ///
+    /// assert!(BulkString::try_from_bytes(Bytes::from_static(b"$6\r\nfoobar\r\n")).is_ok());
+    /// assert_eq!(
+    ///     BulkString::try_from_bytes(Bytes::from_static(b"$6\r\nfoobar\r\n\r\n")).unwrap_err(),
+    ///     RespErrorKind::TrailingBytes
+    /// );
+    /// ```
+    pub fn try_from_bytes(input: Bytes) -> Result<Self, RespError> {
+        let mut index = 0;
+        let is_streamed =
+            Self::while_valid_streamed(&input, &mut index, &input.len(), &Limits::unlimited())?;
+        if index != input.len() {
+            return Err(RespError::new(
+                RespErrorKind::TrailingBytes,
+                &input,
+                &input.len(),
+                index,
+            ));
+        }
+        Ok(Self::from_bytes_unchecked(streamed::reassemble_bytes(
+            input,
+            is_streamed,
+        )))
+    }
+
+    #[inline]
+    pub fn try_from_slice(input: &[u8]) -> Result<Self, RespError> {
+        Self::try_from_bytes(Bytes::copy_from_slice(input))
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated by the global allocator for exactly `length` bytes,
+    /// as required by [`Vec::from_raw_parts`].
+    #[deprecated(
+        since = "0.0.11",
+        note = "use `try_from` with an owned `Vec<u8>`, `String`, `Box<[u8]>` or `BytesMut`"
+    )]
     #[inline]
     pub unsafe fn from_raw(ptr: *mut u8, length: usize) -> Self {
         let vector = Vec::from_raw_parts(ptr, length, length);
         let bytes = Bytes::from(vector);
-        Self::from_bytes(bytes)
+        Self::from_bytes_unchecked(bytes)
     }
 
     pub fn while_valid(input: &[u8], start: &mut usize, end: &usize) -> Result<(), RespError> {
-        let mut index = *start;
-        if index + 4 >= *end {
-            return Err(RespError::InvalidValue);
-        }
-        if input[index] != 0x24 {
-            return Err(RespError::InvalidFirstChar);
-        }
-        index += 1;
-        if input[index] == 0x2d {
-            if input[index + 1] != 0x31 || input[index + 2] != 0x0d || input[index + 3] != 0x0a {
-                return Err(RespError::InvalidNullValue);
-            }
-            *start = index + 4;
-            return Ok(());
-        }
-        if input[index] == 0x30 && input[index + 1] >= 0x30 && input[index + 1] <= 0x39 {
-            return Err(RespError::InvalidLength);
-        }
-        while index < *end && input[index] >= 0x30 && input[index] <= 0x39 {
-            index += 1;
-        }
-        if index + 1 >= *end || input[index] != 0x0d || input[index + 1] != 0x0a {
-            return Err(RespError::InvalidLengthSeparator);
-        }
-        let length = unsafe {
-            String::from_utf8_unchecked(input[*start + 1..index].to_vec())
-                .parse::<usize>()
-                .unwrap()
-        };
-        index += 2;
-        let value_start_index = index;
-        while index < *end
-            && index - value_start_index <= length
-            && input[index] != 0x0d
-            && input[index] != 0x0a
-        {
-            index += 1;
-        }
-        if length != index - value_start_index {
-            return Err(RespError::LengthsNotMatch);
-        }
-        if index + 1 >= *end || input[index] != 0x0d || input[index + 1] != 0x0a {
-            return Err(RespError::InvalidTerminate);
-        }
-        *start = index + 2;
+        Self::while_valid_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Validate a Bulk String declaring no more than [`Limits::max_bulk_length`] bytes,
+    /// or as many per chunk if it is streamed
+    pub fn while_valid_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<(), RespError> {
+        Self::while_valid_streamed(input, start, end, limits)?;
         Ok(())
     }
 
+    /// Validate a Bulk String like [`BulkString::while_valid_with`], returning whether it holds
+    /// streamed values
+    pub(crate) fn while_valid_streamed(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<bool, RespError> {
+        limits.while_valid_frame(input, start, end, |input, start, end| {
+            Self::while_valid_nested(input, start, end, limits)
+        })
+    }
+
+    /// Validate a Bulk String within an aggregate, returning whether it is streamed
+    pub(crate) fn while_valid_nested(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<bool, RespError> {
+        blob::while_valid(0x24, input, start, end, limits)
+    }
+
     pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, RespError> {
+        Self::parse_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Parse a Bulk String declaring no more than [`Limits::max_bulk_length`] bytes,
+    /// or as many per chunk if it is streamed
+    pub fn parse_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        let is_streamed = Self::while_valid_streamed(input, &mut index, end, limits)?;
+        let value = Self::from_bytes_unchecked(streamed::copy(&input[*start..index], is_streamed));
+        *start = index;
+        Ok(value)
+    }
+
+    /// Parse a Bulk String from `input`, sharing its buffer instead of copying it
+    ///
+    /// A streamed Bulk String is reassembled into a new buffer instead.
+    pub fn parse_bytes(input: &Bytes, start: &mut usize) -> Result<Self, RespError> {
+        Self::parse_bytes_with(input, start, &Limits::unlimited())
+    }
+
+    /// Parse a Bulk String from `input` like [`BulkString::parse_bytes`], declaring no more than
+    /// [`Limits::max_bulk_length`] bytes, or as many per chunk if it is streamed
+    pub fn parse_bytes_with(
+        input: &Bytes,
+        start: &mut usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
         let mut index = *start;
-        Self::while_valid(input, &mut index, end)?;
-        let value = Self::from_slice(&input[*start..index]);
+        let is_streamed = Self::while_valid_streamed(input, &mut index, &input.len(), limits)?;
+        let value = Self::from_bytes_unchecked(streamed::reassemble_bytes(
+            input.slice(*start..index),
+            is_streamed,
+        ));
         *start = index;
         Ok(value)
     }
 }
 
+impl TryFrom<Bytes> for BulkString {
+    type Error = RespError;
+
+    fn try_from(input: Bytes) -> Result<Self, RespError> {
+        Self::try_from_bytes(input)
+    }
+}
+
+#[allow(clippy::needless_lifetimes, clippy::partialeq_ne_impl)]
 impl<'a> PartialEq<BulkString> for &'a BulkString {
     fn eq(&self, other: &BulkString) -> bool {
         self.0 == other.bytes()
@@ -158,7 +327,7 @@ impl<'a> PartialEq<BulkString> for &'a BulkString {
 
 #[cfg(test)]
 mod tests_bulk_string {
-    use crate::{BulkString, EMPTY_BULK_STRING, NULL_BULK_STRING};
+    use crate::{BulkString, Limits, RespErrorKind, EMPTY_BULK_STRING, NULL_BULK_STRING};
     use bytes::Bytes;
 
     #[test]
@@ -176,23 +345,66 @@ mod tests_bulk_string {
     #[test]
     fn test_from_bytes() {
         let bulk_string: BulkString =
-            BulkString::from_bytes(Bytes::from_static(b"$6\r\nfoobar\r\n"));
+            BulkString::from_bytes_unchecked(Bytes::from_static(b"$6\r\nfoobar\r\n"));
         assert_eq!(bulk_string.bytes(), Bytes::from_static(b"$6\r\nfoobar\r\n"));
     }
 
     #[test]
     fn test_from_slice() {
         let bulk_string: BulkString =
-            BulkString::from_slice(Vec::from("$6\r\nfoobar\r\n").as_slice());
+            BulkString::from_slice_unchecked(Vec::from("$6\r\nfoobar\r\n").as_slice());
         assert_eq!(bulk_string.bytes(), Bytes::from_static(b"$6\r\nfoobar\r\n"));
     }
 
     #[test]
+    fn test_value() {
+        let bulk_string =
+            BulkString::from_bytes_unchecked(Bytes::from_static(b"$8\r\nfoo\r\nbar\r\n"));
+        assert_eq!(bulk_string.value(), Some(Bytes::from_static(b"foo\r\nbar")));
+        assert_eq!(bulk_string.value_len(), 8);
+        assert_eq!(bulk_string.as_str(), Some("foo\r\nbar"));
+        assert_eq!(bulk_string.to_vec(), Some(Vec::from("foo\r\nbar")));
+    }
+
+    #[test]
+    fn test_value_empty() {
+        assert_eq!(EMPTY_BULK_STRING.value(), Some(Bytes::new()));
+        assert_eq!(EMPTY_BULK_STRING.value_len(), 0);
+        assert_eq!(EMPTY_BULK_STRING.as_str(), Some(""));
+        assert_eq!(EMPTY_BULK_STRING.to_vec(), Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_value_null() {
+        assert_eq!(NULL_BULK_STRING.value(), None);
+        assert_eq!(NULL_BULK_STRING.value_len(), 0);
+        assert_eq!(NULL_BULK_STRING.as_str(), None);
+        assert_eq!(NULL_BULK_STRING.to_vec(), None);
+    }
+
+    #[test]
+    fn test_as_str_invalid_utf8() {
+        let bulk_string = BulkString::new(&[0xff, 0xfe]);
+        assert_eq!(bulk_string.as_str(), None);
+        assert_eq!(bulk_string.value(), Some(Bytes::from_static(&[0xff, 0xfe])));
+    }
+
+    #[test]
+    fn test_value_is_zero_copy() {
+        let bytes = Bytes::from(Vec::from("$6\r\nfoobar\r\n"));
+        let bulk_string = BulkString::from_bytes_unchecked(bytes.clone());
+        let value = bulk_string.value().unwrap();
+        assert_eq!(value.as_ptr(), bytes[4..].as_ptr());
+    }
+
+    #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_is_empty() {
         assert_eq!(EMPTY_BULK_STRING.is_empty(), true)
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_is_null() {
         assert_eq!(NULL_BULK_STRING.is_null(), true)
     }
@@ -229,4 +441,175 @@ mod tests_bulk_string {
         );
         assert_eq!(cursor, 5);
     }
+
+    #[test]
+    fn test_while_valid_incomplete_header() {
+        for string in ["", "$", "$6", "$-", "$-1\r"] {
+            let result = BulkString::while_valid(string.as_bytes(), &mut 0, &string.len());
+            assert!(
+                matches!(
+                    result.map_err(|error| error.kind()),
+                    Err(RespErrorKind::Incomplete { .. })
+                ),
+                "{:?}",
+                string
+            );
+        }
+        let string = "$-1\r";
+        assert_eq!(
+            BulkString::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: Some(1) }
+        );
+    }
+
+    #[test]
+    fn test_while_valid_incomplete_value() {
+        let string = "$6\r";
+        assert_eq!(
+            BulkString::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: Some(9) }
+        );
+        let string = "$6\r\nfoo";
+        assert_eq!(
+            BulkString::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: Some(5) }
+        );
+        let string = "$6\r\nfoobar";
+        assert_eq!(
+            BulkString::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: Some(2) }
+        );
+        let string = "$6\r\nfoobar\r";
+        assert_eq!(
+            BulkString::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: Some(1) }
+        );
+    }
+
+    #[test]
+    fn test_while_valid_invalid_length() {
+        for string in ["$01\r\n", "$00", "$\r\n", "$x\r\n"] {
+            assert_eq!(
+                BulkString::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+                RespErrorKind::InvalidLength,
+                "{:?}",
+                string
+            );
+        }
+    }
+
+    #[test]
+    fn test_while_valid_length_overflow() {
+        let string = format!("${}\r\n", usize::MAX);
+        assert_eq!(
+            BulkString::while_valid_with(
+                string.as_bytes(),
+                &mut 0,
+                &string.len(),
+                &Limits::unlimited()
+            )
+            .unwrap_err(),
+            RespErrorKind::InvalidLength
+        );
+    }
+
+    #[test]
+    fn test_while_valid_invalid_null_value() {
+        let string = "$-2\r\n";
+        assert_eq!(
+            BulkString::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::InvalidNullValue
+        );
+    }
+
+    #[test]
+    fn test_parse_crlf_value() {
+        let string = "$8\r\n\r\nfoo\r\n\r\r\n";
+        let mut cursor = 0;
+        assert_eq!(
+            BulkString::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap(),
+            BulkString::new(b"\r\nfoo\r\n\r")
+        );
+        assert_eq!(cursor, 14);
+    }
+
+    #[test]
+    fn test_parse_binary_value() {
+        let value: Vec<u8> = (0..=255).collect();
+        let bulk_string = BulkString::new(&value);
+        let input = bulk_string.bytes();
+        let mut cursor = 0;
+        assert_eq!(
+            BulkString::parse(&input, &mut cursor, &input.len()).unwrap(),
+            bulk_string
+        );
+        assert_eq!(cursor, input.len());
+    }
+
+    #[test]
+    fn test_while_valid_value_longer_than_length() {
+        let string = "$3\r\nfoobar\r\n";
+        assert_eq!(
+            BulkString::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::InvalidTerminate
+        );
+    }
+
+    #[test]
+    fn test_parse_streamed() {
+        let string = "$?\r\n;4\r\nHell\r\n;5\r\no wor\r\n;1\r\nd\r\n;0\r\n+OK\r\n";
+        let mut cursor = 0;
+        assert_eq!(
+            BulkString::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap(),
+            BulkString::new(b"Hello word")
+        );
+        assert_eq!(cursor, string.len() - 5);
+    }
+
+    #[test]
+    fn test_try_from_bytes() {
+        let bytes = Bytes::from_static(b"$6\r\nfoobar\r\n");
+        let bulk_string = BulkString::try_from_bytes(bytes.clone()).unwrap();
+        assert_eq!(bulk_string.bytes().as_ptr(), bytes.as_ptr());
+        assert_eq!(
+            BulkString::try_from_slice(b"$6\r\nfoo\r\n").unwrap_err(),
+            RespErrorKind::Incomplete { needed: Some(3) }
+        );
+        assert_eq!(
+            BulkString::try_from_slice(b"$3\r\nfoobar\r\n").unwrap_err(),
+            RespErrorKind::InvalidTerminate
+        );
+    }
+
+    #[test]
+    fn test_try_from_bytes_streamed() {
+        let bulk_string =
+            BulkString::try_from_slice(b"$?\r\n;3\r\nfoo\r\n;3\r\nbar\r\n;0\r\n").unwrap();
+        assert_eq!(bulk_string, BulkString::new(b"foobar"));
+    }
+
+    #[test]
+    fn test_parse_bytes() {
+        let input = Bytes::from(Vec::from("$3\r\nfoo\r\n$3\r\nbar"));
+        let mut cursor = 0;
+        let bulk_string = BulkString::parse_bytes(&input, &mut cursor).unwrap();
+        assert_eq!(bulk_string, BulkString::new(b"foo"));
+        assert_eq!(bulk_string.bytes().as_ptr(), input.as_ptr());
+        assert_eq!(cursor, 9);
+        assert_eq!(
+            BulkString::parse_bytes(&input, &mut cursor).unwrap_err(),
+            RespErrorKind::Incomplete { needed: Some(2) }
+        );
+        assert_eq!(cursor, 9);
+    }
+
+    #[test]
+    fn test_parse_bytes_question_mark() {
+        let input = Bytes::from(Vec::from("$5\r\nwhat?\r\n"));
+        let bulk_string = BulkString::parse_bytes(&input, &mut 0).unwrap();
+        assert_eq!(bulk_string, BulkString::new(b"what?"));
+        assert_eq!(bulk_string.bytes().as_ptr(), input.as_ptr());
+        let bulk_string = BulkString::try_from_bytes(input.clone()).unwrap();
+        assert_eq!(bulk_string.bytes().as_ptr(), input.as_ptr());
+    }
 }
@@ -0,0 +1,247 @@
+use crate::decoder::FrameState;
+use crate::{
+    Array, Attribute, BigNumber, BlobError, Boolean, BulkString, Double, Error, Integer, Limits,
+    Map, Null, Push, RespError, RespType, Set, SimpleString, Streamed, VerbatimString,
+};
+use bytes::BytesMut;
+use tokio_util::codec::{Decoder, Encoder};
+
+#[derive(Debug)]
+pub enum RespCodecError {
+    /// A value to encode is longer than [`Limits::max_frame_size`].
+    ///
+    /// Decoding reports a frame that is too long as [`RespCodecError::Resp`] instead, so the
+    /// error keeps where the frame crossed the limit.
+    MaxFrameSizeExceeded,
+    Resp(RespError),
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for RespCodecError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RespCodecError::MaxFrameSizeExceeded => {
+                write!(f, "Max frame size exceeded.")
+            }
+            RespCodecError::Resp(error) => {
+                write!(f, "{}", error)
+            }
+            RespCodecError::Io(error) => {
+                write!(f, "{}", error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RespCodecError {}
+
+impl From<RespError> for RespCodecError {
+    fn from(error: RespError) -> Self {
+        RespCodecError::Resp(error)
+    }
+}
+
+impl From<std::io::Error> for RespCodecError {
+    fn from(error: std::io::Error) -> Self {
+        RespCodecError::Io(error)
+    }
+}
+
+/// Codec for `tokio_util::codec::Framed`
+///
+/// Decodes values into `RespType` and encodes any RESP value.
+#[derive(Debug)]
+pub struct RespCodec {
+    state: FrameState,
+}
+
+impl RespCodec {
+    /// Build a new Codec with the default [`Limits`], without a frame size limit
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::RespCodec;
+    ///
+    /// let codec: RespCodec = RespCodec::new();
+    /// ```
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_limits(Limits::new())
+    }
+
+    /// Build a new Codec rejecting frames longer than `max_frame_size` bytes
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::RespCodec;
+    ///
+    /// let codec: RespCodec = RespCodec::with_max_frame_size(512 * 1024 * 1024);
+    /// ```
+    #[inline]
+    pub fn with_max_frame_size(max_frame_size: usize) -> Self {
+        Self::with_limits(Limits::new().with_max_frame_size(max_frame_size))
+    }
+
+    /// Build a new Codec rejecting values beyond `limits`
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{Limits, RespCodec};
+    ///
+    /// let codec: RespCodec = RespCodec::with_limits(Limits::new().with_max_depth(8));
+    /// ```
+    #[inline]
+    pub fn with_limits(limits: Limits) -> Self {
+        Self {
+            state: FrameState::with_limits(limits),
+        }
+    }
+
+    #[inline]
+    pub fn limits(&self) -> &Limits {
+        self.state.limits()
+    }
+
+    #[inline]
+    pub fn max_frame_size(&self) -> usize {
+        self.limits().max_frame_size()
+    }
+}
+
+impl Default for RespCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Decoder for RespCodec {
+    type Item = RespType;
+    type Error = RespCodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<RespType>, RespCodecError> {
+        match self.state.advance(src) {
+            Ok(Some(frame)) => Ok(Some(frame.split(src))),
+            Ok(None) => Ok(None),
+            Err(error) => Err(RespCodecError::Resp(error)),
+        }
+    }
+}
+
+macro_rules! impl_encoder {
+    ($($type:ty),*) => {
+        $(
+            impl Encoder<$type> for RespCodec {
+                type Error = RespCodecError;
+
+                fn encode(&mut self, item: $type, dst: &mut BytesMut) -> Result<(), RespCodecError> {
+                    if item.len() > self.max_frame_size() {
+                        return Err(RespCodecError::MaxFrameSizeExceeded);
+                    }
+                    dst.extend_from_slice(&item.bytes());
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_encoder!(
+    RespType,
+    SimpleString,
+    Error,
+    Integer,
+    BulkString,
+    Array,
+    Null,
+    Boolean,
+    Double,
+    BigNumber,
+    Map,
+    Set,
+    Push,
+    Attribute,
+    BlobError,
+    VerbatimString,
+    Streamed
+);
+
+#[cfg(test)]
+mod tests_codec {
+    use crate::{
+        ArrayBuilder, BulkString, Integer, RespCodec, RespCodecError, RespErrorKind, RespType,
+        SimpleString,
+    };
+    use bytes::{Bytes, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_decode() {
+        let mut codec = RespCodec::new();
+        let mut buffer = BytesMut::from(&b"+OK\r\n*2\r\n:1\r\n$3\r\nfo"[..]);
+        assert_eq!(
+            codec.decode(&mut buffer).unwrap(),
+            Some(RespType::SimpleString(SimpleString::new(b"OK")))
+        );
+        assert_eq!(codec.decode(&mut buffer).unwrap(), None);
+        buffer.extend_from_slice(b"o\r\n");
+        let array = ArrayBuilder::new()
+            .insert(RespType::Integer(Integer::new(1)))
+            .insert(RespType::BulkString(BulkString::new(b"foo")))
+            .build();
+        assert_eq!(
+            codec.decode(&mut buffer).unwrap(),
+            Some(RespType::Array(array))
+        );
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        let mut codec = RespCodec::new();
+        let mut buffer = BytesMut::from(&b"?\r\n"[..]);
+        assert!(matches!(
+            codec.decode(&mut buffer),
+            Err(RespCodecError::Resp(error)) if error == RespErrorKind::InvalidFirstChar
+        ));
+    }
+
+    #[test]
+    fn test_decode_max_frame_size() {
+        let mut codec = RespCodec::with_max_frame_size(16);
+        let mut buffer = BytesMut::from(&b"$100\r\nfoo"[..]);
+        assert!(matches!(
+            codec.decode(&mut buffer),
+            Err(RespCodecError::Resp(error)) if error == RespErrorKind::MaxFrameSizeExceeded
+        ));
+        let mut codec = RespCodec::with_max_frame_size(16);
+        let mut buffer = BytesMut::from(&b"+0123456789012345678\r\n"[..]);
+        match codec.decode(&mut buffer) {
+            Err(RespCodecError::Resp(error)) => {
+                assert_eq!(error, RespErrorKind::MaxFrameSizeExceeded);
+                assert_eq!(error.offset(), 16);
+            }
+            result => panic!("{:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_encode() {
+        let mut codec = RespCodec::new();
+        let mut buffer = BytesMut::new();
+        codec
+            .encode(RespType::Integer(Integer::new(1)), &mut buffer)
+            .unwrap();
+        codec.encode(BulkString::new(b"foo"), &mut buffer).unwrap();
+        assert_eq!(buffer.freeze(), Bytes::from_static(b":1\r\n$3\r\nfoo\r\n"));
+    }
+
+    #[test]
+    fn test_encode_max_frame_size() {
+        let mut codec = RespCodec::with_max_frame_size(4);
+        let mut buffer = BytesMut::new();
+        assert!(matches!(
+            codec.encode(SimpleString::new(b"QUEUED"), &mut buffer),
+            Err(RespCodecError::MaxFrameSizeExceeded)
+        ));
+    }
+}
@@ -0,0 +1,262 @@
+use crate::{double, Array};
+use bytes::{BufMut, Bytes, BytesMut};
+use std::borrow::Cow;
+
+/// Value which can be sent as an argument of a command, in a Bulk String
+pub trait ToRespArg {
+    /// Bytes of the argument
+    fn to_resp_arg(&self) -> Cow<'_, [u8]>;
+}
+
+impl<T: ToRespArg + ?Sized> ToRespArg for &T {
+    #[inline]
+    fn to_resp_arg(&self) -> Cow<'_, [u8]> {
+        (**self).to_resp_arg()
+    }
+}
+
+impl ToRespArg for [u8] {
+    #[inline]
+    fn to_resp_arg(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl<const N: usize> ToRespArg for [u8; N] {
+    #[inline]
+    fn to_resp_arg(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl ToRespArg for Vec<u8> {
+    #[inline]
+    fn to_resp_arg(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl ToRespArg for str {
+    #[inline]
+    fn to_resp_arg(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self.as_bytes())
+    }
+}
+
+impl ToRespArg for String {
+    #[inline]
+    fn to_resp_arg(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self.as_bytes())
+    }
+}
+
+impl ToRespArg for Bytes {
+    #[inline]
+    fn to_resp_arg(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl ToRespArg for BytesMut {
+    #[inline]
+    fn to_resp_arg(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self)
+    }
+}
+
+macro_rules! impl_to_resp_arg_integer {
+    ($($type:ty),*) => {
+        $(
+            impl ToRespArg for $type {
+                #[inline]
+                fn to_resp_arg(&self) -> Cow<'_, [u8]> {
+                    Cow::Owned(self.to_string().into_bytes())
+                }
+            }
+        )*
+    };
+}
+
+impl_to_resp_arg_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_to_resp_arg_float {
+    ($($type:ty),*) => {
+        $(
+            /// Formatted as by [`Double::new`](crate::Double::new), with infinities as `inf` and `-inf`
+            impl ToRespArg for $type {
+                fn to_resp_arg(&self) -> Cow<'_, [u8]> {
+                    Cow::Owned(double::format(*self).into_bytes())
+                }
+            }
+        )*
+    };
+}
+
+impl_to_resp_arg_float!(f32, f64);
+
+/// Command sent by a client, built into an Array of Bulk Strings
+///
+/// Arguments are encoded as they are added, so building the Array copies them only once.
+///
+/// # Example
+/// ``` rust
+/// use resp_protocol::Command;
+/// use bytes::Bytes;
+///
+/// let array = Command::new("SET").arg("key").arg(b"value").arg("EX").arg(10).build();
+/// assert_eq!(
+///     array.bytes(),
+///     Bytes::from_static(b"*5\r\n$3\r\nSET\r\n$3\r\nkey\r\n$5\r\nvalue\r\n$2\r\nEX\r\n$2\r\n10\r\n")
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Command {
+    count: usize,
+    args: BytesMut,
+}
+
+impl Command {
+    /// Build a new Command, `name` being its first argument
+    #[inline]
+    pub fn new<T: ToRespArg>(name: T) -> Self {
+        let mut command = Self {
+            count: 0,
+            args: BytesMut::new(),
+        };
+        command.arg(name);
+        command
+    }
+
+    /// Add an argument to the Command
+    pub fn arg<T: ToRespArg>(&mut self, arg: T) -> &mut Self {
+        let arg = arg.to_resp_arg();
+        let length_string = arg.len().to_string();
+        self.args.reserve(arg.len() + length_string.len() + 5);
+        self.args.put_u8(0x24); // "$"
+        self.args.put_slice(length_string.as_bytes());
+        self.args.put_u8(0x0d); // CR
+        self.args.put_u8(0x0a); // LF
+        self.args.put_slice(&arg);
+        self.args.put_u8(0x0d); // CR
+        self.args.put_u8(0x0a); // LF
+        self.count += 1;
+        self
+    }
+
+    /// Add each of `args` to the Command
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::Command;
+    ///
+    /// let keys = vec!["foo", "bar"];
+    /// let array = Command::new("DEL").args(&keys).build();
+    /// assert_eq!(array.count(), 3);
+    /// ```
+    pub fn args<I>(&mut self, args: I) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: ToRespArg,
+    {
+        for arg in args {
+            self.arg(arg);
+        }
+        self
+    }
+
+    /// Number of arguments, the name included
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn build(&self) -> Array {
+        let count_string = self.count.to_string();
+        let mut bytes = BytesMut::with_capacity(self.args.len() + count_string.len() + 3);
+        bytes.put_u8(0x2a); // "*"
+        bytes.put_slice(count_string.as_bytes());
+        bytes.put_u8(0x0d); // CR
+        bytes.put_u8(0x0a); // LF
+        bytes.put_slice(&self.args);
+        Array::from_bytes_unchecked(bytes.freeze())
+    }
+}
+
+/// Build a [`Command`] from its name and arguments
+///
+/// Expands to a block returning the Command itself, which may take further arguments.
+///
+/// # Example
+/// ``` rust
+/// use resp_protocol::{cmd, Command};
+///
+/// let mut command: Command = cmd!("SET", "key", b"value");
+/// command.arg("EX").arg(10);
+/// assert_eq!(
+///     command.build(),
+///     Command::new("SET").arg("key").arg(b"value").arg("EX").arg(10).build()
+/// );
+/// ```
+#[macro_export]
+macro_rules! cmd {
+    ($name:expr $(, $arg:expr)* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut command = $crate::Command::new($name);
+        $(
+            command.arg($arg);
+        )*
+        command
+    }};
+}
+
+#[cfg(test)]
+mod tests_command {
+    use crate::{ArrayBuilder, BulkString, Command, RespType, ToRespArg};
+    use bytes::Bytes;
+
+    #[test]
+    fn test_build() {
+        let array = Command::new("SET")
+            .arg("key")
+            .arg(b"value")
+            .arg(String::from("EX"))
+            .arg(10)
+            .build();
+        let expected = ArrayBuilder::new()
+            .insert(RespType::BulkString(BulkString::new(b"SET")))
+            .insert(RespType::BulkString(BulkString::new(b"key")))
+            .insert(RespType::BulkString(BulkString::new(b"value")))
+            .insert(RespType::BulkString(BulkString::new(b"EX")))
+            .insert(RespType::BulkString(BulkString::new(b"10")))
+            .build();
+        assert_eq!(array, expected);
+    }
+
+    #[test]
+    fn test_to_resp_arg() {
+        assert_eq!(&*"foo".to_resp_arg(), b"foo");
+        assert_eq!(&*b"foo"[..].to_resp_arg(), b"foo");
+        assert_eq!(&*vec![0xff_u8].to_resp_arg(), [0xff]);
+        assert_eq!(&*Bytes::from_static(b"foo").to_resp_arg(), b"foo");
+        assert_eq!(&*(-42_i64).to_resp_arg(), b"-42");
+        assert_eq!(&*u64::MAX.to_resp_arg(), b"18446744073709551615");
+        assert_eq!(&*1.5_f64.to_resp_arg(), b"1.5");
+        assert_eq!(&*f64::NEG_INFINITY.to_resp_arg(), b"-inf");
+        assert_eq!(&*0.25_f32.to_resp_arg(), b"0.25");
+    }
+
+    #[test]
+    fn test_cmd() {
+        let key = String::from("key");
+        let command = cmd!("GET", &key);
+        assert_eq!(command.count(), 2);
+        let mut expected = Command::new("GET");
+        expected.arg("key");
+        assert_eq!(command, expected);
+        assert_eq!(
+            command.build().bytes(),
+            Bytes::from_static(b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n")
+        );
+        assert_eq!(cmd!("PING",).build(), Command::new("PING").build());
+    }
+}
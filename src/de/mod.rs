@@ -0,0 +1,885 @@
+//! Deserialization of RESP values into any `Deserialize` type
+//!
+//! Strings are borrowed from the input unless they were streamed. As RESP2 replies carry
+//! numbers and booleans in Bulk Strings, those are parsed when the type asks for them.
+//!
+//! | RESP                                       | Rust                                    |
+//! |--------------------------------------------|-----------------------------------------|
+//! | Simple and Bulk Strings, Verbatim strings  | strings, bytes, numbers, booleans       |
+//! | Integer, Double, Boolean, Big number       | numbers and booleans                    |
+//! | Null, null Bulk Strings and Arrays         | `None`, `()`                            |
+//! | Array, Set, Push                           | sequences and tuples                    |
+//! | Map, Array of keys and values              | maps and structs                        |
+//! | string, Map of one entry, Array of two     | enum variants keyed by their names      |
+//! | Error, Blob error                          | an error quoting the message            |
+//!
+//! A string of lines, such as a `CLIENT LIST` reply, is a sequence of its lines, and a line of
+//! `key=value` pairs separated by spaces is a map. Attributes are skipped for the reply they
+//! describe.
+
+use crate::{ArrayRefIter, RespError, RespErrorKind, RespRef, RespType};
+use serde::de::{self, Deserialize, DeserializeSeed, Visitor};
+use std::borrow::Cow;
+
+/// Deserialize `value`, borrowing strings from its bytes
+///
+/// # Example
+/// ``` rust
+/// use resp_protocol::{de, RespType};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct User<'a> {
+///     name: &'a str,
+///     age: u32,
+/// }
+///
+/// // HGETALL reply in RESP2
+/// let string = "*4\r\n$4\r\nname\r\n$3\r\nfoo\r\n$3\r\nage\r\n$2\r\n42\r\n";
+/// let value = RespType::parse(string.as_bytes(), &mut 0, &string.len()).unwrap();
+/// let user: User = de::from_resp(&value).unwrap();
+/// assert_eq!(user.name, "foo");
+/// assert_eq!(user.age, 42);
+/// ```
+#[inline]
+pub fn from_resp<'de, T: Deserialize<'de>>(value: &'de RespType) -> Result<T, DeserializeError> {
+    T::deserialize(Deserializer::new(value.as_resp_ref()))
+}
+
+/// Deserialize the single value held by `input`, borrowing strings from it
+///
+/// # Example
+/// ``` rust
+/// use resp_protocol::de;
+/// use std::collections::HashMap;
+///
+/// let map: HashMap<&str, f64> = de::from_slice(b"%1\r\n+pi\r\n,3.14\r\n").unwrap();
+/// assert_eq!(map["pi"], 3.14);
+/// ```
+pub fn from_slice<'de, T: Deserialize<'de>>(input: &'de [u8]) -> Result<T, DeserializeError> {
+    let mut index = 0;
+    let value = RespRef::parse(input, &mut index, &input.len())?;
+    if index != input.len() {
+        return Err(
+            RespError::new(RespErrorKind::TrailingBytes, input, &input.len(), index).into(),
+        );
+    }
+    T::deserialize(Deserializer::new(value))
+}
+
+/// Error raised while deserializing RESP
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeserializeError {
+    /// The input does not hold a single valid value.
+    Resp(RespError),
+    /// The value at `path` does not match the type deserialized.
+    Invalid { message: String, path: Vec<usize> },
+}
+
+impl DeserializeError {
+    /// Index of the offending value within each enclosing aggregate, outermost first
+    ///
+    /// Keys and values of a Map are counted separately, as they appear on the wire.
+    #[inline]
+    pub fn path(&self) -> &[usize] {
+        match self {
+            DeserializeError::Resp(error) => error.path(),
+            DeserializeError::Invalid { path, .. } => path,
+        }
+    }
+
+    /// Record that the error was raised within the enclosing aggregates at `path`, outermost first
+    fn nested_in_path<I>(mut self, outer: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        if let DeserializeError::Invalid { path, .. } = &mut self {
+            path.splice(0..0, outer);
+        }
+        self
+    }
+}
+
+impl std::fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DeserializeError::Resp(error) => {
+                write!(f, "{}", error)
+            }
+            DeserializeError::Invalid { message, path } => {
+                write!(f, "{}", message)?;
+                if !path.is_empty() {
+                    write!(f, " at ")?;
+                    for index in path {
+                        write!(f, "[{}]", index)?;
+                    }
+                }
+                write!(f, ".")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DeserializeError {}
+
+impl de::Error for DeserializeError {
+    fn custom<T: std::fmt::Display>(message: T) -> Self {
+        DeserializeError::Invalid {
+            message: message.to_string(),
+            path: Vec::new(),
+        }
+    }
+}
+
+impl From<RespError> for DeserializeError {
+    fn from(error: RespError) -> Self {
+        DeserializeError::Resp(error)
+    }
+}
+
+/// Value being deserialized
+#[derive(Debug, Clone)]
+enum Input<'de> {
+    Value(RespRef<'de>),
+    /// Part of a string, such as a line or one side of a `key=value` pair.
+    Text(Cow<'de, [u8]>),
+}
+
+/// Deserializer of a borrowed RESP value
+#[derive(Debug, Clone)]
+pub struct Deserializer<'de> {
+    input: Input<'de>,
+}
+
+impl<'de> Deserializer<'de> {
+    /// Build a new Deserializer of `value`, or of the reply it describes if it is an Attribute
+    pub fn new(value: RespRef<'de>) -> Self {
+        let mut value = value;
+        while let RespRef::Attribute(attribute) = value {
+            value = ArrayRefIter::new(0x7c, attribute.as_bytes())
+                .last()
+                .expect("Attribute holds a reply");
+        }
+        Self {
+            input: Input::Value(value),
+        }
+    }
+
+    #[inline]
+    fn text(text: Cow<'de, [u8]>) -> Self {
+        Self {
+            input: Input::Text(text),
+        }
+    }
+
+    fn is_null(&self) -> bool {
+        match &self.input {
+            Input::Value(RespRef::Null(_)) => true,
+            Input::Value(RespRef::BulkString(bulk_string)) => bulk_string.is_null(),
+            Input::Value(RespRef::Array(array)) => array.is_null(),
+            _ => false,
+        }
+    }
+
+    /// Value of a string-like type, Integers, Doubles and Big numbers included as sent
+    fn as_text(&self) -> Option<Cow<'de, [u8]>> {
+        match &self.input {
+            Input::Value(RespRef::SimpleString(simple_string)) => {
+                Some(Cow::Borrowed(simple_string.value()))
+            }
+            Input::Value(RespRef::BulkString(bulk_string)) => bulk_string.value(),
+            Input::Value(RespRef::VerbatimString(verbatim_string)) => {
+                Some(Cow::Borrowed(verbatim_string.value()))
+            }
+            Input::Value(RespRef::Integer(integer)) => Some(Cow::Borrowed(integer.raw_value())),
+            Input::Value(RespRef::Double(double)) => Some(Cow::Borrowed(double.raw_value())),
+            Input::Value(RespRef::BigNumber(big_number)) => Some(Cow::Borrowed(big_number.value())),
+            Input::Text(text) => Some(text.clone()),
+            _ => None,
+        }
+    }
+
+    /// Values of an Array, a Set or a Push, or keys and values of a Map
+    fn values(&self) -> Option<ArrayRefIter<'de>> {
+        match &self.input {
+            Input::Value(RespRef::Array(array)) if !array.is_null() => Some(array.iter()),
+            Input::Value(RespRef::Set(set)) => Some(set.iter()),
+            Input::Value(RespRef::Push(push)) => Some(push.iter()),
+            Input::Value(RespRef::Map(map)) => Some(ArrayRefIter::new(0x25, map.as_bytes())),
+            _ => None,
+        }
+    }
+
+    fn deserialize_number<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        match &self.input {
+            Input::Value(RespRef::Integer(integer)) => visitor.visit_i64(integer.value()),
+            Input::Value(RespRef::Double(double)) => visitor.visit_f64(double.value()),
+            Input::Value(RespRef::Boolean(_)) => de::Deserializer::deserialize_any(self, visitor),
+            _ => match self.as_text() {
+                Some(text) => visit_number(text, visitor),
+                None => de::Deserializer::deserialize_any(self, visitor),
+            },
+        }
+    }
+}
+
+/// Visit `text` as the number it holds, or as a string
+fn visit_number<'de, V: Visitor<'de>>(
+    text: Cow<'de, [u8]>,
+    visitor: V,
+) -> Result<V::Value, DeserializeError> {
+    let string = match std::str::from_utf8(&text) {
+        Ok(string) => string,
+        Err(_) => return visit_text(text, visitor),
+    };
+    if let Ok(number) = string.parse::<i64>() {
+        visitor.visit_i64(number)
+    } else if let Ok(number) = string.parse::<u64>() {
+        visitor.visit_u64(number)
+    } else if let Ok(number) = string.parse::<i128>() {
+        visitor.visit_i128(number)
+    } else if let Ok(number) = string.parse::<u128>() {
+        visitor.visit_u128(number)
+    } else if let Ok(number) = string.parse::<f64>() {
+        visitor.visit_f64(number)
+    } else {
+        visit_text(text, visitor)
+    }
+}
+
+/// Visit `text` as a string if it is valid UTF-8, or as bytes
+fn visit_text<'de, V: Visitor<'de>>(
+    text: Cow<'de, [u8]>,
+    visitor: V,
+) -> Result<V::Value, DeserializeError> {
+    match text {
+        Cow::Borrowed(bytes) => match std::str::from_utf8(bytes) {
+            Ok(string) => visitor.visit_borrowed_str(string),
+            Err(_) => visitor.visit_borrowed_bytes(bytes),
+        },
+        Cow::Owned(bytes) => match String::from_utf8(bytes) {
+            Ok(string) => visitor.visit_string(string),
+            Err(error) => visitor.visit_byte_buf(error.into_bytes()),
+        },
+    }
+}
+
+/// Split `text` into its non-empty parts, without their `\r`
+fn split_text<'de>(text: &Cow<'de, [u8]>, separator: u8) -> Vec<Cow<'de, [u8]>> {
+    let parts = |text: &'de [u8]| {
+        text.split(move |byte| *byte == separator)
+            .map(|part| part.strip_suffix(b"\r").unwrap_or(part))
+            .filter(|part| !part.is_empty())
+    };
+    match text {
+        Cow::Borrowed(text) => parts(text).map(Cow::Borrowed).collect(),
+        Cow::Owned(text) => text
+            .split(|byte| *byte == separator)
+            .map(|part| part.strip_suffix(b"\r").unwrap_or(part))
+            .filter(|part| !part.is_empty())
+            .map(|part| Cow::Owned(part.to_vec()))
+            .collect(),
+    }
+}
+
+/// Keys and values of a line of `key=value` pairs separated by spaces
+fn text_entries<'de>(text: &Cow<'de, [u8]>) -> Vec<Deserializer<'de>> {
+    let mut entries = Vec::new();
+    for pair in split_text(text, 0x20) {
+        let (key, value) = match pair {
+            Cow::Borrowed(pair) => match pair.iter().position(|byte| *byte == 0x3d) {
+                Some(index) => (
+                    Cow::Borrowed(&pair[..index]),
+                    Cow::Borrowed(&pair[index + 1..]),
+                ),
+                None => (Cow::Borrowed(pair), Cow::Borrowed(&pair[pair.len()..])),
+            },
+            Cow::Owned(pair) => match pair.iter().position(|byte| *byte == 0x3d) {
+                Some(index) => (
+                    Cow::Owned(pair[..index].to_vec()),
+                    Cow::Owned(pair[index + 1..].to_vec()),
+                ),
+                None => (Cow::Owned(pair), Cow::Owned(Vec::new())),
+            },
+        };
+        entries.push(Deserializer::text(key));
+        entries.push(Deserializer::text(value));
+    }
+    entries
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'de> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        if self.is_null() {
+            return visitor.visit_unit();
+        }
+        let value = match &self.input {
+            Input::Text(text) => return visit_text(text.clone(), visitor),
+            Input::Value(value) => *value,
+        };
+        match value {
+            RespRef::Integer(integer) => visitor.visit_i64(integer.value()),
+            RespRef::Double(double) => visitor.visit_f64(double.value()),
+            RespRef::Boolean(boolean) => visitor.visit_bool(boolean.value()),
+            RespRef::BigNumber(big_number) => {
+                visit_number(Cow::Borrowed(big_number.value()), visitor)
+            }
+            RespRef::Map(_) => visitor.visit_map(MapAccess::new(
+                self.values()
+                    .expect("Map holds values")
+                    .map(Deserializer::new),
+            )),
+            RespRef::Array(_) | RespRef::Set(_) | RespRef::Push(_) => visit_seq(
+                self.values()
+                    .expect("aggregate holds values")
+                    .map(Deserializer::new),
+                visitor,
+            ),
+            RespRef::Error(error) => Err(error_reply(error.value())),
+            RespRef::BlobError(blob_error) => Err(error_reply(blob_error.value())),
+            _ => visit_text(self.as_text().expect("value is a string"), visitor),
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        match &self.input {
+            Input::Value(RespRef::Boolean(boolean)) => visitor.visit_bool(boolean.value()),
+            Input::Value(RespRef::Integer(integer)) if matches!(integer.value(), 0 | 1) => {
+                visitor.visit_bool(integer.value() == 1)
+            }
+            _ => match self.as_text().as_deref() {
+                Some(b"1") | Some(b"true") => visitor.visit_bool(true),
+                Some(b"0") | Some(b"false") => visitor.visit_bool(false),
+                _ => self.deserialize_any(visitor),
+            },
+        }
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        self.deserialize_number(visitor)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        self.deserialize_number(visitor)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        self.deserialize_number(visitor)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        self.deserialize_number(visitor)
+    }
+
+    fn deserialize_i128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        self.deserialize_number(visitor)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        self.deserialize_number(visitor)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        self.deserialize_number(visitor)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        self.deserialize_number(visitor)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        self.deserialize_number(visitor)
+    }
+
+    fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        self.deserialize_number(visitor)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        self.deserialize_number(visitor)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        self.deserialize_number(visitor)
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        match self.as_text() {
+            Some(text) => visit_text(text, visitor),
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        match self.as_text() {
+            Some(Cow::Borrowed(bytes)) => visitor.visit_borrowed_bytes(bytes),
+            Some(Cow::Owned(bytes)) => visitor.visit_byte_buf(bytes),
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        if self.is_null() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        match &self.input {
+            Input::Value(RespRef::Map(_)) => self.deserialize_any(visitor),
+            _ => match self.values() {
+                Some(values) => visit_seq(values.map(Deserializer::new), visitor),
+                None => match self.as_text() {
+                    Some(text) => visit_seq(
+                        split_text(&text, 0x0a).into_iter().map(Deserializer::text),
+                        visitor,
+                    ),
+                    None => self.deserialize_any(visitor),
+                },
+            },
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _length: usize,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _length: usize,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        match self.values() {
+            Some(values) => visitor.visit_map(MapAccess::new(values.map(Deserializer::new))),
+            None => match (&self.input, self.as_text()) {
+                (Input::Value(RespRef::Integer(_)), _)
+                | (Input::Value(RespRef::Double(_)), _)
+                | (Input::Value(RespRef::BigNumber(_)), _)
+                | (_, None) => self.deserialize_any(visitor),
+                (_, Some(text)) => {
+                    visitor.visit_map(MapAccess::new(text_entries(&text).into_iter()))
+                }
+            },
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        if let Some(mut values) = self.values().filter(|values| values.clone().count() == 2) {
+            let variant = Deserializer::new(values.next().expect("variant has a name"));
+            let value = Deserializer::new(values.next().expect("variant has a value"));
+            return visitor.visit_enum(EnumAccess {
+                variant,
+                value: Some(value),
+            });
+        }
+        match self.as_text() {
+            Some(_) => visitor.visit_enum(EnumAccess {
+                variant: self,
+                value: None,
+            }),
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        visitor.visit_unit()
+    }
+}
+
+fn error_reply(message: &[u8]) -> DeserializeError {
+    de::Error::custom(format_args!("error reply \"{}\"", message.escape_ascii()))
+}
+
+/// Visit the values of a sequence, failing if `visitor` leaves any of them
+fn visit_seq<'de, I, V>(values: I, visitor: V) -> Result<V::Value, DeserializeError>
+where
+    I: Iterator<Item = Deserializer<'de>>,
+    V: Visitor<'de>,
+{
+    let mut seq = SeqAccess::new(values);
+    let value = visitor.visit_seq(&mut seq)?;
+    let remaining = seq.values.count();
+    if remaining > 0 {
+        let length = seq.index + remaining;
+        return Err(
+            <DeserializeError as de::Error>::invalid_length(length, &"fewer values")
+                .nested_in_path(Some(seq.index)),
+        );
+    }
+    Ok(value)
+}
+
+/// Access to the values of a sequence, recording the index of a failing value
+struct SeqAccess<I> {
+    values: I,
+    index: usize,
+}
+
+impl<I> SeqAccess<I> {
+    #[inline]
+    fn new(values: I) -> Self {
+        Self { values, index: 0 }
+    }
+}
+
+impl<'de, I> de::SeqAccess<'de> for SeqAccess<I>
+where
+    I: Iterator<Item = Deserializer<'de>>,
+{
+    type Error = DeserializeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, DeserializeError> {
+        let value = match self.values.next() {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        let index = self.index;
+        self.index += 1;
+        seed.deserialize(value)
+            .map(Some)
+            .map_err(|error| error.nested_in_path(Some(index)))
+    }
+}
+
+/// Access to the keys and values of a map, recording the index of a failing key or value
+struct MapAccess<I> {
+    values: I,
+    index: usize,
+}
+
+impl<I> MapAccess<I> {
+    #[inline]
+    fn new(values: I) -> Self {
+        Self { values, index: 0 }
+    }
+}
+
+impl<'de, I> de::MapAccess<'de> for MapAccess<I>
+where
+    I: Iterator<Item = Deserializer<'de>>,
+{
+    type Error = DeserializeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, DeserializeError> {
+        let key = match self.values.next() {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+        let index = self.index;
+        self.index += 1;
+        seed.deserialize(key)
+            .map(Some)
+            .map_err(|error| error.nested_in_path(Some(index)))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, DeserializeError> {
+        let index = self.index;
+        self.index += 1;
+        let value = self.values.next().ok_or_else(|| {
+            <DeserializeError as de::Error>::custom("missing value after the last key")
+                .nested_in_path(Some(index))
+        })?;
+        seed.deserialize(value)
+            .map_err(|error| error.nested_in_path(Some(index)))
+    }
+}
+
+/// Access to an enum variant, its name alone for a unit variant
+struct EnumAccess<'de> {
+    variant: Deserializer<'de>,
+    value: Option<Deserializer<'de>>,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess<'de> {
+    type Error = DeserializeError;
+    type Variant = VariantAccess<'de>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, VariantAccess<'de>), DeserializeError> {
+        let path = self.value.as_ref().map(|_| 0);
+        let variant = seed
+            .deserialize(self.variant)
+            .map_err(|error| error.nested_in_path(path))?;
+        Ok((variant, VariantAccess { value: self.value }))
+    }
+}
+
+struct VariantAccess<'de> {
+    value: Option<Deserializer<'de>>,
+}
+
+impl<'de> VariantAccess<'de> {
+    fn value(self) -> Result<Deserializer<'de>, DeserializeError> {
+        self.value.ok_or_else(|| {
+            de::Error::custom("expected a variant with a value, found its name alone")
+        })
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess<'de> {
+    type Error = DeserializeError;
+
+    fn unit_variant(self) -> Result<(), DeserializeError> {
+        match self.value {
+            None => Ok(()),
+            Some(value) => {
+                de::IgnoredAny::deserialize(value)
+                    .map_err(|error| error.nested_in_path(Some(1)))?;
+                Ok(())
+            }
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, DeserializeError> {
+        seed.deserialize(self.value()?)
+            .map_err(|error| error.nested_in_path(Some(1)))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _length: usize,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        de::Deserializer::deserialize_seq(self.value()?, visitor)
+            .map_err(|error| error.nested_in_path(Some(1)))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        de::Deserializer::deserialize_map(self.value()?, visitor)
+            .map_err(|error| error.nested_in_path(Some(1)))
+    }
+}
+
+#[cfg(test)]
+mod tests_de {
+    use crate::de::{from_resp, from_slice, DeserializeError};
+    use crate::{ser, Protocol, RespErrorKind, RespType};
+    use serde::{Deserialize, Serialize};
+    use std::collections::HashMap;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct User<'a> {
+        name: &'a str,
+        age: u32,
+        admin: bool,
+        email: Option<String>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Event {
+        Ping,
+        Moved(u16),
+        Pair(i64, bool),
+        Set { key: String },
+    }
+
+    #[test]
+    fn test_struct_from_resp2_and_resp3() {
+        let expected = User {
+            name: "foo",
+            age: 42,
+            admin: true,
+            email: None,
+        };
+        let input = b"*6\r\n$4\r\nname\r\n$3\r\nfoo\r\n$3\r\nage\r\n$2\r\n42\r\n\
+                      $5\r\nadmin\r\n$1\r\n1\r\n";
+        let user: User = from_slice(input).unwrap();
+        assert_eq!(user, expected);
+        assert_eq!(user.name.as_ptr(), input[18..].as_ptr());
+        let input = b"%4\r\n+name\r\n+foo\r\n+age\r\n:42\r\n+admin\r\n#t\r\n+email\r\n_\r\n";
+        assert_eq!(from_slice::<User>(input).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_xrange() {
+        let input = b"*2\r\n\
+                      *2\r\n$3\r\n1-0\r\n*2\r\n$1\r\na\r\n$1\r\n1\r\n\
+                      *2\r\n$3\r\n2-0\r\n*4\r\n$1\r\na\r\n$1\r\n2\r\n$1\r\nb\r\n$3\r\n2.5\r\n";
+        let value = RespType::try_from_slice(input).unwrap();
+        let entries: Vec<(&str, HashMap<&str, f64>)> = from_resp(&value).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].0, "1-0");
+        assert_eq!(entries[1].1["b"], 2.5);
+    }
+
+    #[test]
+    fn test_client_list() {
+        #[derive(Deserialize)]
+        struct Client<'a> {
+            id: u64,
+            name: &'a str,
+            db: u8,
+        }
+
+        let input = b"$57\r\nid=3 addr=127.0.0.1:1 name= db=0\r\nid=4 name=worker db=2\r\n\r\n";
+        let clients: Vec<Client> = from_slice(input).unwrap();
+        assert_eq!(clients.len(), 2);
+        assert_eq!((clients[0].id, clients[0].name, clients[0].db), (3, "", 0));
+        assert_eq!(
+            (clients[1].id, clients[1].name, clients[1].db),
+            (4, "worker", 2)
+        );
+    }
+
+    #[test]
+    fn test_enum_round_trip() {
+        let events = vec![
+            Event::Ping,
+            Event::Moved(7000),
+            Event::Pair(-1, true),
+            Event::Set {
+                key: "k".to_owned(),
+            },
+        ];
+        for protocol in [Protocol::Resp2, Protocol::Resp3] {
+            let value = ser::to_resp_with(&events, protocol).unwrap();
+            assert_eq!(from_resp::<Vec<Event>>(&value).unwrap(), events);
+        }
+    }
+
+    #[test]
+    fn test_error_path() {
+        let input = b"*2\r\n%1\r\n+age\r\n:1\r\n%1\r\n+age\r\n+old\r\n";
+        let error = from_slice::<Vec<HashMap<String, u32>>>(input).unwrap_err();
+        assert_eq!(error.path(), &[1, 1]);
+        assert_eq!(
+            error.to_string(),
+            "invalid type: string \"old\", expected u32 at [1][1]."
+        );
+        let error = from_slice::<Vec<String>>(b"*1\r\n-ERR wrong type\r\n").unwrap_err();
+        assert_eq!(error.to_string(), "error reply \"ERR wrong type\" at [0].");
+        let error = from_slice::<u8>(b":300\r\n").unwrap_err();
+        assert!(matches!(error, DeserializeError::Invalid { .. }));
+    }
+
+    #[test]
+    fn test_sequence_length() {
+        let input = b"*3\r\n:1\r\n:2\r\n:3\r\n";
+        assert_eq!(from_slice::<(i64, i64, i64)>(input).unwrap(), (1, 2, 3));
+        let error = from_slice::<(i64, i64)>(input).unwrap_err();
+        assert_eq!(error.path(), &[2]);
+        assert_eq!(
+            error.to_string(),
+            "invalid length 3, expected fewer values at [2]."
+        );
+        assert_eq!(from_slice::<[i64; 2]>(input).unwrap_err(), error);
+        let error = from_slice::<Vec<(i64,)>>(b"*1\r\n*2\r\n:1\r\n:2\r\n").unwrap_err();
+        assert_eq!(error.path(), &[0, 1]);
+        let error = from_slice::<(i64, i64)>(b"*1\r\n:1\r\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid length 1, expected a tuple of size 2."
+        );
+    }
+
+    #[test]
+    fn test_from_slice_invalid() {
+        match from_slice::<String>(b"+OK\r\n+OK\r\n").unwrap_err() {
+            DeserializeError::Resp(error) => assert_eq!(error, RespErrorKind::TrailingBytes),
+            error => panic!("{:?}", error),
+        }
+        assert!(matches!(
+            from_slice::<String>(b"$3\r\nfo").unwrap_err(),
+            DeserializeError::Resp(_)
+        ));
+    }
+
+    #[test]
+    fn test_streamed_and_attribute() {
+        let input = b"|1\r\n+ttl\r\n:3600\r\n*?\r\n$?\r\n;2\r\nfo\r\n;1\r\no\r\n;0\r\n.\r\n";
+        let value: Vec<String> = from_slice(input).unwrap();
+        assert_eq!(value, vec!["foo".to_owned()]);
+        let value: Option<Vec<String>> = from_slice(b"*-1\r\n").unwrap();
+        assert_eq!(value, None);
+    }
+}
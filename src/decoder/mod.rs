@@ -0,0 +1,542 @@
+use crate::{aggregate, blob, streamed, Limits, RespError, RespErrorKind, RespType};
+use bytes::BytesMut;
+
+/// Open aggregate or streamed string of a frame
+#[derive(Debug, PartialEq)]
+enum Pending {
+    /// Values of an aggregate, `validated` out of `count`.
+    Values {
+        first_char: u8,
+        count: usize,
+        validated: usize,
+    },
+    /// Values validated so far in a streamed aggregate.
+    Streamed { first_char: u8, count: usize },
+    /// Chunks of a streamed string.
+    Chunks,
+}
+
+/// Outcome of a validation step
+enum Step {
+    /// A complete value.
+    Value,
+    /// The header of an aggregate or streamed string.
+    Open(Pending),
+    /// A chunk of a streamed string.
+    Chunk,
+    /// The end of a streamed aggregate or string, completing its value.
+    Close,
+}
+
+/// Complete frame at the front of a buffer
+#[derive(Debug, PartialEq)]
+pub(crate) struct Frame {
+    pub(crate) length: usize,
+    streamed: bool,
+}
+
+impl Frame {
+    /// Split the frame off the buffer, reassembling it if it holds streamed values
+    pub(crate) fn split(&self, buffer: &mut BytesMut) -> RespType {
+        let bytes = buffer.split_to(self.length).freeze();
+        if self.streamed {
+            RespType::from_bytes_unchecked(streamed::reassemble(&bytes))
+        } else {
+            RespType::from_bytes_unchecked(bytes)
+        }
+    }
+}
+
+/// Validation progress of the frame at the front of a buffer
+///
+/// Aggregates are tracked on an explicit stack, so a frame arriving in many chunks
+/// is validated exactly once, whatever its size or nesting.
+#[derive(Debug, Default)]
+pub(crate) struct FrameState {
+    /// Length of the already validated part of the frame.
+    index: usize,
+    /// Open aggregates and streamed strings, innermost last.
+    pending: Vec<Pending>,
+    /// Whether the frame holds streamed values.
+    streamed: bool,
+    /// Buffer length below which validation cannot make progress.
+    needed: usize,
+    /// Limits enforced on every frame.
+    limits: Limits,
+}
+
+impl FrameState {
+    #[inline]
+    pub(crate) fn with_limits(limits: Limits) -> Self {
+        Self {
+            limits,
+            ..Self::default()
+        }
+    }
+
+    #[inline]
+    pub(crate) fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    /// Continue validating the frame at the front of `input`
+    ///
+    /// Returns the frame once it is complete, or `None` if more input is needed.
+    pub(crate) fn advance(&mut self, input: &[u8]) -> Result<Option<Frame>, RespError> {
+        let end = input.len();
+        if end < self.needed {
+            return Ok(None);
+        }
+        let max_frame_size = self.limits.max_frame_size();
+        let frame_end = end.min(max_frame_size);
+        loop {
+            let mut index = self.index;
+            let step = match self.step(input, &mut index, &frame_end) {
+                Ok(step) => step,
+                Err(error) => {
+                    if let RespErrorKind::Incomplete { needed } = error.kind() {
+                        let needed = frame_end.saturating_add(needed.unwrap_or(1));
+                        if needed > max_frame_size {
+                            return Err(self.nest(RespError::new(
+                                RespErrorKind::MaxFrameSizeExceeded,
+                                input,
+                                &end,
+                                max_frame_size,
+                            )));
+                        }
+                        self.needed = needed;
+                        return Ok(None);
+                    }
+                    return Err(self.nest(error));
+                }
+            };
+            self.index = index;
+            match step {
+                Step::Open(pending) => {
+                    self.streamed |= !matches!(pending, Pending::Values { .. });
+                    self.pending.push(pending);
+                    continue;
+                }
+                Step::Chunk => continue,
+                Step::Close => {
+                    self.pending.pop();
+                }
+                Step::Value => {}
+            }
+            loop {
+                match self.pending.last_mut() {
+                    None => {
+                        let frame = Frame {
+                            length: self.index,
+                            streamed: self.streamed,
+                        };
+                        self.reset();
+                        return Ok(Some(frame));
+                    }
+                    Some(Pending::Values {
+                        count, validated, ..
+                    }) => {
+                        *validated += 1;
+                        if *validated < *count {
+                            break;
+                        }
+                        self.pending.pop();
+                    }
+                    Some(Pending::Streamed { count, .. }) => {
+                        *count += 1;
+                        break;
+                    }
+                    Some(Pending::Chunks) => unreachable!("chunks are not values"),
+                }
+            }
+        }
+    }
+
+    /// Validate the next value, header, chunk or end marker
+    fn step(&self, input: &[u8], index: &mut usize, end: &usize) -> Result<Step, RespError> {
+        if *index >= *end {
+            return Err(RespError::incomplete(None, input, end));
+        }
+        // Chunks are never followed by values, so every pending entry below a value is an aggregate.
+        let depth = self.pending.len();
+        match self.pending.last() {
+            Some(Pending::Chunks) => {
+                match blob::while_valid_chunk(input, index, end, &self.limits)? {
+                    0 => Ok(Step::Close),
+                    _ => Ok(Step::Chunk),
+                }
+            }
+            Some(Pending::Streamed { first_char, count }) if input[*index] == 0x2e => {
+                aggregate::while_valid_streamed_end(*first_char, *count, input, index, end)?;
+                Ok(Step::Close)
+            }
+            Some(Pending::Streamed { first_char, count }) => {
+                self.limits
+                    .check_element_count(
+                        aggregate::entries(*first_char, count + 1),
+                        input,
+                        end,
+                        *index,
+                    )
+                    .map_err(|error| error.within(*first_char))?;
+                self.step_value(input, index, end, depth)
+            }
+            _ => self.step_value(input, index, end, depth),
+        }
+    }
+
+    /// Validate the next value, or header of an aggregate or streamed string, within `depth` aggregates
+    fn step_value(
+        &self,
+        input: &[u8],
+        index: &mut usize,
+        end: &usize,
+        depth: usize,
+    ) -> Result<Step, RespError> {
+        let first_char = input[*index];
+        match first_char {
+            _ if first_char == 0x24 && *index + 1 < *end && input[*index + 1] == 0x3f => {
+                blob::while_valid_streamed_header(input, index, end)?;
+                Ok(Step::Open(Pending::Chunks))
+            }
+            _ if aggregate::is_aggregate(first_char) => {
+                self.limits
+                    .check_depth(depth + 1, input, end, *index)
+                    .map_err(|error| error.within(first_char))?;
+                match aggregate::while_valid_header(first_char, input, index, end, &self.limits)? {
+                    Some(0) => Ok(Step::Value),
+                    Some(count) => Ok(Step::Open(Pending::Values {
+                        first_char,
+                        count,
+                        validated: 0,
+                    })),
+                    None => Ok(Step::Open(Pending::Streamed {
+                        first_char,
+                        count: 0,
+                    })),
+                }
+            }
+            _ => {
+                RespType::while_valid_nested(input, index, end, &self.limits, depth)?;
+                Ok(Step::Value)
+            }
+        }
+    }
+
+    /// Record the path of the value being validated within the open aggregates
+    fn nest(&self, error: RespError) -> RespError {
+        let aggregates = self.pending.iter().filter_map(|pending| match pending {
+            Pending::Values {
+                first_char,
+                validated,
+                ..
+            } => Some((*first_char, *validated)),
+            Pending::Streamed { first_char, count } => Some((*first_char, *count)),
+            Pending::Chunks => None,
+        });
+        match aggregates.clone().next_back() {
+            Some((first_char, _)) => error
+                .within(first_char)
+                .nested_in_path(aggregates.map(|(_, index)| index)),
+            None => error,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn reset(&mut self) {
+        self.index = 0;
+        self.pending.clear();
+        self.streamed = false;
+        self.needed = 0;
+    }
+}
+
+/// Streaming decoder
+///
+/// Buffers chunks of input as they arrive and yields complete values one at a time.
+/// Values are split off the internal buffer without copying.
+#[derive(Debug, Default)]
+pub struct Decoder {
+    buffer: BytesMut,
+    state: FrameState,
+}
+
+impl Decoder {
+    /// Build a new Decoder
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::Decoder;
+    ///
+    /// let decoder: Decoder = Decoder::new();
+    /// ```
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buffer: BytesMut::with_capacity(capacity),
+            state: FrameState::default(),
+        }
+    }
+
+    /// Build a new Decoder rejecting values beyond `limits`
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{Decoder, Limits, RespErrorKind};
+    ///
+    /// let mut decoder = Decoder::with_limits(Limits::new().with_max_bulk_length(4));
+    /// decoder.extend_from_slice(b"$9999999999\r\n");
+    /// assert_eq!(decoder.decode().unwrap_err(), RespErrorKind::MaxBulkLengthExceeded);
+    /// ```
+    #[inline]
+    pub fn with_limits(limits: Limits) -> Self {
+        Self {
+            buffer: BytesMut::new(),
+            state: FrameState::with_limits(limits),
+        }
+    }
+
+    #[inline]
+    pub fn limits(&self) -> &Limits {
+        self.state.limits()
+    }
+
+    /// Append a chunk of input to the Decoder
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::Decoder;
+    ///
+    /// let mut decoder: Decoder = Decoder::new();
+    /// decoder.extend_from_slice(b"+O");
+    /// assert_eq!(decoder.decode().unwrap(), None);
+    /// decoder.extend_from_slice(b"K\r\n");
+    /// assert!(decoder.decode().unwrap().is_some());
+    /// ```
+    #[inline]
+    pub fn extend_from_slice(&mut self, chunk: &[u8]) {
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// Internal buffer, e.g. to read from a socket into it directly
+    ///
+    /// Bytes may only be appended to the buffer.
+    #[inline]
+    pub fn buffer_mut(&mut self) -> &mut BytesMut {
+        &mut self.buffer
+    }
+
+    /// Number of buffered bytes that were not decoded yet
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Decode the next complete value
+    ///
+    /// Returns `None` while the buffered input ends in the middle of a value.
+    /// Once an error is returned the input can not be recovered, and the connection should be closed.
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{Decoder, RespType, SimpleString};
+    ///
+    /// let mut decoder: Decoder = Decoder::new();
+    /// decoder.extend_from_slice(b"+OK\r\n+QUEUED\r\n");
+    /// assert_eq!(decoder.decode().unwrap(), Some(RespType::SimpleString(SimpleString::new(b"OK"))));
+    /// assert_eq!(decoder.decode().unwrap(), Some(RespType::SimpleString(SimpleString::new(b"QUEUED"))));
+    /// assert_eq!(decoder.decode().unwrap(), None);
+    /// ```
+    pub fn decode(&mut self) -> Result<Option<RespType>, RespError> {
+        match self.state.advance(&self.buffer)? {
+            Some(frame) => Ok(Some(frame.split(&mut self.buffer))),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_decoder {
+    use crate::decoder::Pending;
+    use crate::{
+        ArrayBuilder, BulkString, Decoder, Integer, RespErrorKind, RespType, SimpleString,
+    };
+
+    #[test]
+    fn test_decode_frames() {
+        let mut decoder = Decoder::new();
+        decoder.extend_from_slice(b"+OK\r\n:100\r\n$3\r\nfoo\r\n");
+        assert_eq!(
+            decoder.decode().unwrap(),
+            Some(RespType::SimpleString(SimpleString::new(b"OK")))
+        );
+        assert_eq!(
+            decoder.decode().unwrap(),
+            Some(RespType::Integer(Integer::new(100)))
+        );
+        assert_eq!(
+            decoder.decode().unwrap(),
+            Some(RespType::BulkString(BulkString::new(b"foo")))
+        );
+        assert_eq!(decoder.decode().unwrap(), None);
+        assert!(decoder.is_empty());
+    }
+
+    #[test]
+    fn test_decode_byte_by_byte() {
+        let string = b"*3\r\n:1\r\n*2\r\n$3\r\nfoo\r\n*0\r\n$-1\r\n+OK\r\n";
+        let mut decoder = Decoder::new();
+        let mut frames = Vec::new();
+        for byte in string.iter() {
+            decoder.extend_from_slice(&[*byte]);
+            while let Some(frame) = decoder.decode().unwrap() {
+                frames.push(frame);
+            }
+        }
+        let subarray = ArrayBuilder::new()
+            .insert(RespType::BulkString(BulkString::new(b"foo")))
+            .insert(RespType::Array(ArrayBuilder::new().build()))
+            .build();
+        let array = ArrayBuilder::new()
+            .insert(RespType::Integer(Integer::new(1)))
+            .insert(RespType::Array(subarray))
+            .insert(RespType::BulkString(crate::NULL_BULK_STRING))
+            .build();
+        assert_eq!(
+            frames,
+            vec![
+                RespType::Array(array),
+                RespType::SimpleString(SimpleString::new(b"OK"))
+            ]
+        );
+        assert!(decoder.is_empty());
+    }
+
+    #[test]
+    fn test_decode_large_bulk_string_in_chunks() {
+        let value = vec![0x0d; 64 * 1024];
+        let bulk_string = BulkString::new(&value);
+        let bytes = bulk_string.bytes();
+        let mut decoder = Decoder::new();
+        let mut chunks = bytes.chunks(4096);
+        decoder.extend_from_slice(chunks.next().unwrap());
+        assert_eq!(decoder.decode().unwrap(), None);
+        assert_eq!(decoder.state.needed, bytes.len());
+        for chunk in chunks {
+            assert_eq!(decoder.decode().unwrap(), None);
+            decoder.extend_from_slice(chunk);
+        }
+        assert_eq!(
+            decoder.decode().unwrap(),
+            Some(RespType::BulkString(bulk_string))
+        );
+    }
+
+    #[test]
+    fn test_decode_resumes_array() {
+        let mut decoder = Decoder::new();
+        decoder.extend_from_slice(b"*3\r\n:1\r\n:2\r\n:");
+        assert_eq!(decoder.decode().unwrap(), None);
+        assert_eq!(decoder.state.index, 12);
+        assert_eq!(
+            decoder.state.pending,
+            vec![Pending::Values {
+                first_char: 0x2a,
+                count: 3,
+                validated: 2
+            }]
+        );
+        decoder.extend_from_slice(b"3\r\n");
+        assert!(decoder.decode().unwrap().is_some());
+        assert_eq!(decoder.state.index, 0);
+    }
+
+    #[test]
+    fn test_decode_resp3_aggregates_byte_by_byte() {
+        let string = b"|1\r\n+ttl\r\n:1\r\n%1\r\n~1\r\n:1\r\n>0\r\n>1\r\n+pong\r\n";
+        let mut decoder = Decoder::new();
+        let mut frames = Vec::new();
+        for byte in string.iter() {
+            decoder.extend_from_slice(&[*byte]);
+            while let Some(frame) = decoder.decode().unwrap() {
+                frames.push(frame.bytes());
+            }
+        }
+        assert_eq!(
+            frames,
+            vec![
+                &b"|1\r\n+ttl\r\n:1\r\n%1\r\n~1\r\n:1\r\n>0\r\n"[..],
+                &b">1\r\n+pong\r\n"[..]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_streamed_byte_by_byte() {
+        let string =
+            b"*?\r\n$?\r\n;3\r\nfoo\r\n;3\r\nbar\r\n;0\r\n%?\r\n+a\r\n:1\r\n.\r\n.\r\n:2\r\n";
+        let mut decoder = Decoder::new();
+        let mut frames = Vec::new();
+        for byte in string.iter() {
+            decoder.extend_from_slice(&[*byte]);
+            while let Some(frame) = decoder.decode().unwrap() {
+                frames.push(frame.bytes());
+            }
+        }
+        assert_eq!(
+            frames,
+            vec![
+                &b"*2\r\n$6\r\nfoobar\r\n%1\r\n+a\r\n:1\r\n"[..],
+                &b":2\r\n"[..]
+            ]
+        );
+        assert!(decoder.is_empty());
+    }
+
+    #[test]
+    fn test_decode_streamed_resumes_chunks() {
+        let mut decoder = Decoder::new();
+        decoder.extend_from_slice(b"$?\r\n;3\r\nfoo\r\n;3\r\nba");
+        assert_eq!(decoder.decode().unwrap(), None);
+        assert_eq!(decoder.state.index, 13);
+        assert_eq!(decoder.state.pending, vec![Pending::Chunks]);
+        decoder.extend_from_slice(b"r\r\n;0\r\n");
+        assert_eq!(
+            decoder.decode().unwrap(),
+            Some(RespType::BulkString(BulkString::new(b"foobar")))
+        );
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        let mut decoder = Decoder::new();
+        decoder.extend_from_slice(b"*1\r\n?\r\n");
+        assert_eq!(
+            decoder.decode().unwrap_err(),
+            RespErrorKind::InvalidFirstChar
+        );
+    }
+
+    #[test]
+    fn test_decode_error_path() {
+        let mut decoder = Decoder::new();
+        decoder.extend_from_slice(b"*2\r\n:1\r\n~?\r\n:1\r\n");
+        assert_eq!(decoder.decode().unwrap(), None);
+        decoder.extend_from_slice(b"$3\r\nfooo\r\n");
+        let error = decoder.decode().unwrap_err();
+        assert_eq!(error, RespErrorKind::InvalidTerminate);
+        assert_eq!(error.path(), &[1, 1]);
+        assert_eq!(error.offset(), 23);
+        assert_eq!(error.type_name(), Some("BulkString"));
+    }
+}
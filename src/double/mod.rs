@@ -0,0 +1,410 @@
+use crate::{Limits, RespError, RespErrorKind};
+use bytes::{BufMut, Bytes, BytesMut};
+use std::convert::TryFrom;
+use std::fmt::LowerExp;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Double(pub(crate) Bytes);
+
+/// Text of a float as held by a Double, `inf`, `-inf` or `nan` for the special values
+///
+/// Finite values are written with the shortest digits that read back as the same `T`, in
+/// exponent notation when that is shorter, as in `1e300`.
+pub(crate) fn format<T>(input: T) -> String
+where
+    T: Copy + Into<f64> + ToString + LowerExp,
+{
+    let value: f64 = input.into();
+    if value.is_nan() {
+        "nan".to_owned()
+    } else if value == f64::INFINITY {
+        "inf".to_owned()
+    } else if value == f64::NEG_INFINITY {
+        "-inf".to_owned()
+    } else {
+        let plain = input.to_string();
+        let exponent = format!("{:e}", input);
+        if exponent.len() < plain.len() {
+            exponent
+        } else {
+            plain
+        }
+    }
+}
+
+/// Double type (RESP3)
+impl Double {
+    /// Build a new Double
+    ///
+    /// # Example
+    /// ```
+    /// use resp_protocol::Double;
+    ///
+    /// let double = Double::new(1.25);
+    /// println!("{:?}", double); // Double(b",1.25\r\n")
+    /// ```
+    #[inline]
+    pub fn new(input: f64) -> Self {
+        Self::from_float(input)
+    }
+
+    /// Build a new Double from any float, held in the shortest form of its own type
+    pub(crate) fn from_float<T>(input: T) -> Self
+    where
+        T: Copy + Into<f64> + ToString + LowerExp,
+    {
+        let string = format(input);
+        let mut bytes = BytesMut::with_capacity(string.len() + 3);
+        bytes.put_u8(0x2c); // ","
+        bytes.put_slice(string.as_bytes());
+        bytes.put_u8(0x0d); // CR
+        bytes.put_u8(0x0a); // LF
+        Self::from_bytes_unchecked(bytes.freeze())
+    }
+
+    /// Value of the Double
+    ///
+    /// Returns `NaN` if the Double was built from bytes that do not hold a valid value.
+    ///
+    /// # Example
+    /// ```
+    /// use resp_protocol::Double;
+    ///
+    /// let double = Double::new(-1.5e-3);
+    /// assert_eq!(double.value(), -1.5e-3);
+    /// ```
+    #[inline]
+    pub fn value(&self) -> f64 {
+        std::str::from_utf8(&self.raw_value())
+            .ok()
+            .and_then(|string| string.parse::<f64>().ok())
+            .unwrap_or(f64::NAN)
+    }
+
+    #[inline]
+    pub fn raw_value(&self) -> Vec<u8> {
+        let length = self.0.len();
+        self.0.slice(1..(length - 2)).to_vec()
+    }
+
+    #[inline]
+    pub fn bytes(&self) -> Bytes {
+        self.0.clone()
+    }
+
+    #[allow(clippy::len_without_is_empty)]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Validate a value against `[+|-]<integral>[.<fractional>][<E|e>[+|-]<exponent>]`,
+    /// or one of `inf`, `-inf` and `nan`
+    pub fn validate_value(input: &[u8]) -> Result<(), RespError> {
+        if input == b"inf" || input == b"-inf" || input == b"nan" {
+            return Ok(());
+        }
+        let length = input.len();
+        let mut index = 0;
+        if index < length && (input[index] == 0x2b || input[index] == 0x2d) {
+            index += 1;
+        }
+        let integral_start_index = index;
+        while index < length && input[index].is_ascii_digit() {
+            index += 1;
+        }
+        if index == integral_start_index {
+            return Err(
+                RespError::new(RespErrorKind::InvalidValue, input, &length, index)
+                    .expecting("digit"),
+            );
+        }
+        if index < length && input[index] == 0x2e {
+            index += 1;
+            let fractional_start_index = index;
+            while index < length && input[index].is_ascii_digit() {
+                index += 1;
+            }
+            if index == fractional_start_index {
+                return Err(
+                    RespError::new(RespErrorKind::InvalidValue, input, &length, index)
+                        .expecting("digit"),
+                );
+            }
+        }
+        if index < length && (input[index] == 0x45 || input[index] == 0x65) {
+            index += 1;
+            if index < length && (input[index] == 0x2b || input[index] == 0x2d) {
+                index += 1;
+            }
+            let exponent_start_index = index;
+            while index < length && input[index].is_ascii_digit() {
+                index += 1;
+            }
+            if index == exponent_start_index {
+                return Err(
+                    RespError::new(RespErrorKind::InvalidValue, input, &length, index)
+                        .expecting("digit"),
+                );
+            }
+        }
+        if index != length {
+            return Err(RespError::new(
+                RespErrorKind::InvalidValue,
+                input,
+                &length,
+                index,
+            ));
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn from_bytes_unchecked(input: Bytes) -> Self {
+        Self(input)
+    }
+
+    #[inline]
+    pub fn from_slice_unchecked(input: &[u8]) -> Self {
+        let bytes = Bytes::copy_from_slice(input);
+        Self::from_bytes_unchecked(bytes)
+    }
+
+    /// Build a new Double from `input`, checking that it holds exactly one valid value
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{Double, RespErrorKind};
+    /// use bytes::Bytes;
+    ///
+    /// assert!(Double::try_from_bytes(Bytes::from_static(b",1.5\r\n")).is_ok());
+    /// assert_eq!(
+    ///     Double::try_from_bytes(Bytes::from_static(b",1.5\r\n\r\n")).unwrap_err(),
+    ///     RespErrorKind::TrailingBytes
+    /// );
+    /// ```
+    pub fn try_from_bytes(input: Bytes) -> Result<Self, RespError> {
+        let mut index = 0;
+        Self::while_valid(&input, &mut index, &input.len())?;
+        if index != input.len() {
+            return Err(RespError::new(
+                RespErrorKind::TrailingBytes,
+                &input,
+                &input.len(),
+                index,
+            ));
+        }
+        Ok(Self::from_bytes_unchecked(input))
+    }
+
+    #[inline]
+    pub fn try_from_slice(input: &[u8]) -> Result<Self, RespError> {
+        Self::try_from_bytes(Bytes::copy_from_slice(input))
+    }
+
+    pub fn while_valid(input: &[u8], start: &mut usize, end: &usize) -> Result<(), RespError> {
+        Self::while_valid_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Validate a Double, whose text may not be longer than [`Limits::max_inline_length`]
+    pub fn while_valid_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<(), RespError> {
+        limits.while_valid_frame(input, start, end, |input, start, end| {
+            Self::while_valid_nested(input, start, end, limits)
+        })
+    }
+
+    pub(crate) fn while_valid_nested(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<(), RespError> {
+        let mut index = *start;
+        if index >= *end {
+            return Err(RespError::incomplete(None, input, end).within(0x2c));
+        }
+        if input[index] != 0x2c {
+            return Err(
+                RespError::new(RespErrorKind::InvalidFirstChar, input, end, index)
+                    .expecting(",")
+                    .within(0x2c),
+            );
+        }
+        index += 1;
+        while index < *end && input[index] != 0x0d && input[index] != 0x0a {
+            index += 1;
+        }
+        limits
+            .check_inline_length(*start + 1, index, input, end)
+            .map_err(|error| error.within(0x2c))?;
+        if index >= *end {
+            return Err(RespError::incomplete(None, input, end).within(0x2c));
+        }
+        if input[index] != 0x0d {
+            return Err(
+                RespError::new(RespErrorKind::InvalidTerminate, input, end, index)
+                    .expecting("\r")
+                    .within(0x2c),
+            );
+        }
+        Self::validate_value(&input[*start + 1..index])
+            .map_err(|error| error.rebase(input, end, *start + 1).within(0x2c))?;
+        if index + 1 >= *end {
+            return Err(RespError::incomplete(Some(1), input, end).within(0x2c));
+        }
+        if input[index + 1] != 0x0a {
+            return Err(
+                RespError::new(RespErrorKind::InvalidTerminate, input, end, index + 1)
+                    .expecting("\n")
+                    .within(0x2c),
+            );
+        }
+        *start = index + 2;
+        Ok(())
+    }
+
+    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, RespError> {
+        Self::parse_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Parse a Double, whose text may not be longer than [`Limits::max_inline_length`]
+    pub fn parse_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        Self::while_valid_with(input, &mut index, end, limits)?;
+        let value = Self::from_slice_unchecked(&input[*start..index]);
+        *start = index;
+        Ok(value)
+    }
+
+    /// Parse a Double from `input`, sharing its buffer instead of copying it
+    pub fn parse_bytes(input: &Bytes, start: &mut usize) -> Result<Self, RespError> {
+        Self::parse_bytes_with(input, start, &Limits::unlimited())
+    }
+
+    /// Parse a Double from `input` like [`Double::parse_bytes`], whose text may not be longer than
+    /// [`Limits::max_inline_length`]
+    pub fn parse_bytes_with(
+        input: &Bytes,
+        start: &mut usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        Self::while_valid_with(input, &mut index, &input.len(), limits)?;
+        let value = Self::from_bytes_unchecked(input.slice(*start..index));
+        *start = index;
+        Ok(value)
+    }
+}
+
+impl TryFrom<Bytes> for Double {
+    type Error = RespError;
+
+    fn try_from(input: Bytes) -> Result<Self, RespError> {
+        Self::try_from_bytes(input)
+    }
+}
+
+#[cfg(test)]
+mod tests_double {
+    use crate::double::Double;
+    use crate::RespErrorKind;
+    use bytes::Bytes;
+
+    #[test]
+    fn test_new() {
+        assert_eq!(Double::new(1.25), Double(Bytes::from_static(b",1.25\r\n")));
+        assert_eq!(Double::new(-10.0), Double(Bytes::from_static(b",-10\r\n")));
+        assert_eq!(
+            Double::new(f64::INFINITY),
+            Double(Bytes::from_static(b",inf\r\n"))
+        );
+        assert_eq!(
+            Double::new(f64::NEG_INFINITY),
+            Double(Bytes::from_static(b",-inf\r\n"))
+        );
+        assert_eq!(
+            Double::new(f64::NAN),
+            Double(Bytes::from_static(b",nan\r\n"))
+        );
+        assert_eq!(
+            Double::new(1e300),
+            Double(Bytes::from_static(b",1e300\r\n"))
+        );
+        assert_eq!(
+            Double::new(-1.5e-300),
+            Double(Bytes::from_static(b",-1.5e-300\r\n"))
+        );
+        assert_eq!(Double::new(100.0), Double(Bytes::from_static(b",100\r\n")));
+        assert_eq!(Double::new(1e300).value(), 1e300);
+    }
+
+    #[test]
+    fn test_value() {
+        assert_eq!(Double(Bytes::from_static(b",1.25\r\n")).value(), 1.25);
+        assert_eq!(Double(Bytes::from_static(b",1.5E+3\r\n")).value(), 1500.0);
+        assert_eq!(
+            Double(Bytes::from_static(b",-inf\r\n")).value(),
+            f64::NEG_INFINITY
+        );
+        assert!(Double(Bytes::from_static(b",nan\r\n")).value().is_nan());
+    }
+
+    #[test]
+    fn test_validate_valid_value() {
+        for value in [
+            "0", "-1", "+1.5", "1.25", "1e10", "1.2E-3", "inf", "-inf", "nan",
+        ] {
+            assert_eq!(
+                Double::validate_value(value.as_bytes()),
+                Ok(()),
+                "{}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_invalid_value() {
+        for value in [
+            "", "-", ".5", "5.", "1e", "1.2.3", "infinity", "NaN", "0x10",
+        ] {
+            assert_eq!(
+                Double::validate_value(value.as_bytes()).unwrap_err(),
+                RespErrorKind::InvalidValue,
+                "{}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let string = ",1.23\r\n+bar\r\n";
+        let mut cursor = 0;
+        let end = string.len();
+        assert_eq!(
+            Double::parse(string.as_bytes(), &mut cursor, &end).unwrap(),
+            Double::new(1.23)
+        );
+        assert_eq!(cursor, 7);
+    }
+
+    #[test]
+    fn test_while_valid_incomplete() {
+        let string = ",1.2";
+        assert_eq!(
+            Double::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: None }
+        );
+    }
+}
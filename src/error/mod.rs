@@ -1,8 +1,9 @@
-use crate::RespError;
-use bytes::{Buf, BufMut, Bytes, BytesMut};
+use crate::{Limits, RespError, RespErrorKind};
+use bytes::{BufMut, Bytes, BytesMut};
+use std::convert::TryFrom;
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct Error(Bytes);
+pub struct Error(pub(crate) Bytes);
 
 /// Error type
 impl Error {
@@ -21,7 +22,7 @@ impl Error {
         bytes.put_slice(input);
         bytes.put_u8(0x0d); // CR
         bytes.put_u8(0x0a); // LF
-        Self::from_bytes(bytes.freeze())
+        Self::from_bytes_unchecked(bytes.freeze())
     }
 
     #[inline]
@@ -29,6 +30,7 @@ impl Error {
         self.0.clone()
     }
 
+    #[allow(clippy::len_without_is_empty)]
     #[inline]
     pub fn len(&self) -> usize {
         self.0.len()
@@ -37,13 +39,7 @@ impl Error {
     #[inline]
     pub fn value(&self) -> Vec<u8> {
         let length = self.0.len();
-        let mut bytes = self.0.slice(1..(length - 2));
-        let mut vector = Vec::<u8>::with_capacity(length - 3);
-        unsafe {
-            vector.set_len(length - 3);
-        }
-        bytes.copy_to_slice(vector.as_mut_slice());
-        vector
+        self.0.slice(1..(length - 2)).to_vec()
     }
 
     pub fn validate_value(input: &[u8]) -> Result<(), RespError> {
@@ -53,57 +49,200 @@ impl Error {
             index += 1;
         }
         if index != length {
-            return Err(RespError::InvalidValue);
+            return Err(RespError::new(
+                RespErrorKind::InvalidValue,
+                input,
+                &length,
+                index,
+            ));
         }
         Ok(())
     }
 
     #[inline]
-    pub fn from_bytes(input: Bytes) -> Self {
+    pub fn from_bytes_unchecked(input: Bytes) -> Self {
         Self(input)
     }
 
     #[inline]
-    pub fn from_slice(input: &[u8]) -> Self {
+    pub fn from_slice_unchecked(input: &[u8]) -> Self {
         let bytes = Bytes::copy_from_slice(input);
-        Self::from_bytes(bytes)
+        Self::from_bytes_unchecked(bytes)
+    }
+
+    #[deprecated(since = "0.0.11", note = "use from_bytes_unchecked")]
+    #[inline]
+    pub fn from_bytes(input: Bytes) -> Self {
+        Self::from_bytes_unchecked(input)
+    }
+
+    #[deprecated(since = "0.0.11", note = "use from_slice_unchecked")]
+    #[inline]
+    pub fn from_slice(input: &[u8]) -> Self {
+        Self::from_slice_unchecked(input)
+    }
+
+    /// Build a new Error from `input`, checking that it holds exactly one valid value
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{Error, RespErrorKind};
+    /// use bytes::Bytes;
+    ///
+    /// assert!(Error::try_from_bytes(Bytes::from_static(b"-ERR\r\n")).is_ok());
+    /// assert_eq!(
+    ///     Error::try_from_bytes(Bytes::from_static(b"-ERR\r\n\r\n")).unwrap_err(),
+    ///     RespErrorKind::TrailingBytes
+    /// );
+    /// ```
+    pub fn try_from_bytes(input: Bytes) -> Result<Self, RespError> {
+        let mut index = 0;
+        Self::while_valid(&input, &mut index, &input.len())?;
+        if index != input.len() {
+            return Err(RespError::new(
+                RespErrorKind::TrailingBytes,
+                &input,
+                &input.len(),
+                index,
+            ));
+        }
+        Ok(Self::from_bytes_unchecked(input))
     }
 
+    #[inline]
+    pub fn try_from_slice(input: &[u8]) -> Result<Self, RespError> {
+        Self::try_from_bytes(Bytes::copy_from_slice(input))
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated by the global allocator for exactly `length` bytes,
+    /// as required by [`Vec::from_raw_parts`].
+    #[deprecated(
+        since = "0.0.11",
+        note = "use `try_from` with an owned `Vec<u8>`, `String`, `Box<[u8]>` or `BytesMut`"
+    )]
     #[inline]
     pub unsafe fn from_raw(ptr: *mut u8, length: usize) -> Self {
         let vector = Vec::from_raw_parts(ptr, length, length);
         let bytes = Bytes::from(vector);
-        Self::from_bytes(bytes)
+        Self::from_bytes_unchecked(bytes)
     }
 
     pub fn while_valid(input: &[u8], start: &mut usize, end: &usize) -> Result<(), RespError> {
+        Self::while_valid_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Validate an Error, whose message may not be longer than [`Limits::max_inline_length`]
+    pub fn while_valid_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<(), RespError> {
+        limits.while_valid_frame(input, start, end, |input, start, end| {
+            Self::while_valid_nested(input, start, end, limits)
+        })
+    }
+
+    pub(crate) fn while_valid_nested(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<(), RespError> {
         let mut index = *start;
-        if index >= *end || input[index] != 0x2d {
-            return Err(RespError::InvalidFirstChar);
+        if index >= *end {
+            return Err(RespError::incomplete(None, input, end).within(0x2d));
+        }
+        if input[index] != 0x2d {
+            return Err(
+                RespError::new(RespErrorKind::InvalidFirstChar, input, end, index)
+                    .expecting("-")
+                    .within(0x2d),
+            );
         }
         index += 1;
         while index < *end && input[index] != 0x0d && input[index] != 0x0a {
             index += 1;
         }
-        if index + 1 >= *end || input[index] != 0x0d || input[index + 1] != 0x0a {
-            return Err(RespError::InvalidTerminate);
+        limits
+            .check_inline_length(*start + 1, index, input, end)
+            .map_err(|error| error.within(0x2d))?;
+        if index >= *end {
+            return Err(RespError::incomplete(None, input, end).within(0x2d));
+        }
+        if input[index] != 0x0d {
+            return Err(
+                RespError::new(RespErrorKind::InvalidTerminate, input, end, index)
+                    .expecting("\r")
+                    .within(0x2d),
+            );
+        }
+        if index + 1 >= *end {
+            return Err(RespError::incomplete(Some(1), input, end).within(0x2d));
+        }
+        if input[index + 1] != 0x0a {
+            return Err(
+                RespError::new(RespErrorKind::InvalidTerminate, input, end, index + 1)
+                    .expecting("\n")
+                    .within(0x2d),
+            );
         }
         *start = index + 2;
         Ok(())
     }
 
     pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, RespError> {
+        Self::parse_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Parse an Error, whose message may not be longer than [`Limits::max_inline_length`]
+    pub fn parse_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
         let mut index = *start;
-        Self::while_valid(input, &mut index, end)?;
-        let value = Self::from_slice(&input[*start..index]);
+        Self::while_valid_with(input, &mut index, end, limits)?;
+        let value = Self::from_slice_unchecked(&input[*start..index]);
         *start = index;
         Ok(value)
     }
+
+    /// Parse an Error from `input`, sharing its buffer instead of copying it
+    pub fn parse_bytes(input: &Bytes, start: &mut usize) -> Result<Self, RespError> {
+        Self::parse_bytes_with(input, start, &Limits::unlimited())
+    }
+
+    /// Parse an Error from `input` like [`Error::parse_bytes`], whose message may not be longer
+    /// than [`Limits::max_inline_length`]
+    pub fn parse_bytes_with(
+        input: &Bytes,
+        start: &mut usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        Self::while_valid_with(input, &mut index, &input.len(), limits)?;
+        let value = Self::from_bytes_unchecked(input.slice(*start..index));
+        *start = index;
+        Ok(value)
+    }
+}
+
+impl TryFrom<Bytes> for Error {
+    type Error = RespError;
+
+    fn try_from(input: Bytes) -> Result<Self, RespError> {
+        Self::try_from_bytes(input)
+    }
 }
 
 #[cfg(test)]
 mod tests_error {
     use crate::error::Error;
+    use crate::RespErrorKind;
     use bytes::Bytes;
 
     #[test]
@@ -151,4 +290,18 @@ mod tests_error {
         );
         assert_eq!(cursor, 17);
     }
+
+    #[test]
+    fn test_while_valid_incomplete() {
+        let string = "-ERR";
+        assert_eq!(
+            Error::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: None }
+        );
+        let string = "-ERR\r";
+        assert_eq!(
+            Error::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: Some(1) }
+        );
+    }
 }
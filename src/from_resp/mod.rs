@@ -0,0 +1,664 @@
+use crate::resp_error::type_name;
+use crate::{ArrayIter, Null, RespType};
+use bytes::Bytes;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::hash::{BuildHasher, Hash};
+use std::iter::FromIterator;
+
+/// Number of bytes of the offending value shown by an error
+const MAX_SHOWN_VALUE: usize = 32;
+
+/// Error raised while converting a RESP value into a Rust type
+///
+/// Records the type expected, the RESP type found in its place, the offending value when it
+/// has the right type but cannot be converted, and the path of that value within its
+/// enclosing aggregates.
+///
+/// # Example
+/// ``` rust
+/// use resp_protocol::{ArrayBuilder, BulkString, FromResp, RespType};
+///
+/// let value = RespType::Array(
+///     ArrayBuilder::new()
+///         .insert(RespType::BulkString(BulkString::new(b"1")))
+///         .insert(RespType::BulkString(BulkString::new(b"two")))
+///         .build(),
+/// );
+/// let error = Vec::<i64>::from_resp(&value).unwrap_err();
+/// assert_eq!(error.expected(), "i64");
+/// assert_eq!(error.found(), "BulkString");
+/// assert_eq!(error.value(), Some(&b"two"[..]));
+/// assert_eq!(error.path(), &[1]);
+/// println!("{}", error); // Expected i64, found BulkString "two" at [1].
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FromRespError {
+    expected: &'static str,
+    found: &'static str,
+    value: Option<Bytes>,
+    path: Vec<usize>,
+}
+
+impl FromRespError {
+    /// Build a new error for `value`, which is not of the `expected` type
+    ///
+    /// Errors sent by the server are recorded with their message.
+    pub fn new(expected: &'static str, value: &RespType) -> Self {
+        let found = match value {
+            RespType::BulkString(bulk_string) if bulk_string.is_null() => "null BulkString",
+            RespType::Array(array) if array.is_null() => "null Array",
+            value => type_name(value.bytes()[0]).expect("RespType has a known type byte"),
+        };
+        let error = Self {
+            expected,
+            found,
+            value: None,
+            path: Vec::new(),
+        };
+        match value {
+            RespType::Error(resp_error) => error.with_value(Bytes::from(resp_error.value())),
+            RespType::BlobError(blob_error) => error.with_value(blob_error.value()),
+            _ => error,
+        }
+    }
+
+    /// Build a new error for a value of the `expected` type which is absent, such as a field
+    #[inline]
+    pub fn missing(expected: &'static str) -> Self {
+        Self {
+            expected,
+            found: "nothing",
+            value: None,
+            path: Vec::new(),
+        }
+    }
+
+    /// Record the offending value, of the right type but not convertible
+    #[inline]
+    pub fn with_value(mut self, value: Bytes) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    /// Record that the error was raised within the enclosing aggregates at `path`, outermost first
+    #[inline]
+    pub fn nested_in_path<I>(mut self, path: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        self.path.splice(0..0, path);
+        self
+    }
+
+    /// Type expected, such as `i64` or `String`
+    #[inline]
+    pub fn expected(&self) -> &'static str {
+        self.expected
+    }
+
+    /// RESP type found, such as `BulkString` or `null Array`
+    #[inline]
+    pub fn found(&self) -> &'static str {
+        self.found
+    }
+
+    /// Offending value, or the message of an Error found
+    #[inline]
+    pub fn value(&self) -> Option<&[u8]> {
+        self.value.as_deref()
+    }
+
+    /// Index of the value within each enclosing aggregate, outermost first
+    ///
+    /// Keys and values of a Map are counted separately, as they appear on the wire.
+    #[inline]
+    pub fn path(&self) -> &[usize] {
+        &self.path
+    }
+}
+
+impl std::fmt::Display for FromRespError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Expected {}, found {}", self.expected, self.found)?;
+        if let Some(value) = &self.value {
+            write!(
+                f,
+                " \"{}\"",
+                value[..value.len().min(MAX_SHOWN_VALUE)].escape_ascii()
+            )?;
+            if value.len() > MAX_SHOWN_VALUE {
+                write!(f, "...")?;
+            }
+        }
+        if !self.path.is_empty() {
+            write!(f, " at ")?;
+            for index in &self.path {
+                write!(f, "[{}]", index)?;
+            }
+        }
+        write!(f, ".")
+    }
+}
+
+impl std::error::Error for FromRespError {}
+
+/// Conversion from a RESP value
+///
+/// Implemented for strings, bytes, numbers and booleans, and for `Option`, `Vec`, tuples,
+/// `HashMap` and `BTreeMap` of convertible types. `u8` is left out, so that `Vec<u8>` holds
+/// the bytes of a string. An Attribute is converted as the reply it describes.
+///
+/// # Example
+/// ``` rust
+/// use resp_protocol::{FromResp, RespType};
+/// use std::collections::HashMap;
+///
+/// let string = "*4\r\n$4\r\nname\r\n$3\r\nfoo\r\n$3\r\nage\r\n$2\r\n42\r\n";
+/// let value = RespType::parse(string.as_bytes(), &mut 0, &string.len()).unwrap();
+/// let hash = HashMap::<String, String>::from_resp(&value).unwrap();
+/// assert_eq!(hash["age"], "42");
+/// ```
+pub trait FromResp: Sized {
+    fn from_resp(value: &RespType) -> Result<Self, FromRespError>;
+}
+
+impl FromResp for RespType {
+    #[inline]
+    fn from_resp(value: &RespType) -> Result<Self, FromRespError> {
+        Ok(value.clone())
+    }
+}
+
+/// Reply described by `value` if it is an Attribute, or `value` itself
+fn reply(value: &RespType) -> Cow<'_, RespType> {
+    let mut value = Cow::Borrowed(value);
+    while let RespType::Attribute(attribute) = &*value {
+        let reply = attribute.reply();
+        value = Cow::Owned(reply);
+    }
+    value
+}
+
+/// Value of a string-like type, Integers and Doubles included as sent
+fn text(value: &RespType) -> Option<Bytes> {
+    match value {
+        RespType::SimpleString(simple_string) => Some(Bytes::from(simple_string.value())),
+        RespType::BulkString(bulk_string) => bulk_string.value(),
+        RespType::VerbatimString(verbatim_string) => Some(verbatim_string.value()),
+        RespType::BigNumber(big_number) => Some(Bytes::from(big_number.value())),
+        RespType::Integer(integer) => Some(Bytes::from(integer.raw_value())),
+        RespType::Double(double) => Some(Bytes::from(double.raw_value())),
+        _ => None,
+    }
+}
+
+/// Parse the text of a string-like `value`
+fn parse_text<T: std::str::FromStr>(
+    expected: &'static str,
+    value: &RespType,
+) -> Result<T, FromRespError> {
+    let text = text(value).ok_or_else(|| FromRespError::new(expected, value))?;
+    std::str::from_utf8(&text)
+        .ok()
+        .and_then(|string| string.parse().ok())
+        .ok_or_else(|| FromRespError::new(expected, value).with_value(text))
+}
+
+impl FromResp for Bytes {
+    fn from_resp(value: &RespType) -> Result<Self, FromRespError> {
+        let value = &*reply(value);
+        text(value).ok_or_else(|| FromRespError::new("Bytes", value))
+    }
+}
+
+impl FromResp for Vec<u8> {
+    fn from_resp(value: &RespType) -> Result<Self, FromRespError> {
+        let value = &*reply(value);
+        text(value)
+            .map(|text| text.to_vec())
+            .ok_or_else(|| FromRespError::new("Vec<u8>", value))
+    }
+}
+
+impl FromResp for String {
+    fn from_resp(value: &RespType) -> Result<Self, FromRespError> {
+        let value = &*reply(value);
+        let text = text(value).ok_or_else(|| FromRespError::new("String", value))?;
+        String::from_utf8(text.to_vec())
+            .map_err(|_| FromRespError::new("String", value).with_value(text))
+    }
+}
+
+macro_rules! impl_from_resp_integer {
+    ($($type:ty),*) => {
+        $(
+            impl FromResp for $type {
+                fn from_resp(value: &RespType) -> Result<Self, FromRespError> {
+                    let value = &*reply(value);
+                    match value {
+                        RespType::Integer(integer) => <$type>::try_from(integer.value()).map_err(|_| {
+                            FromRespError::new(stringify!($type), value)
+                                .with_value(Bytes::from(integer.raw_value()))
+                        }),
+                        RespType::SimpleString(_)
+                        | RespType::BulkString(_)
+                        | RespType::VerbatimString(_)
+                        | RespType::BigNumber(_) => parse_text(stringify!($type), value),
+                        _ => Err(FromRespError::new(stringify!($type), value)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_resp_integer!(i8, i16, i32, i64, i128, isize, u16, u32, u64, u128, usize);
+
+macro_rules! impl_from_resp_float {
+    ($($type:ty),*) => {
+        $(
+            /// Converted from a Double, an Integer, or a string such as `1.5` or `inf`
+            impl FromResp for $type {
+                fn from_resp(value: &RespType) -> Result<Self, FromRespError> {
+                    let value = &*reply(value);
+                    match value {
+                        RespType::Double(double) => Ok(double.value() as $type),
+                        RespType::Integer(integer) => Ok(integer.value() as $type),
+                        RespType::SimpleString(_)
+                        | RespType::BulkString(_)
+                        | RespType::VerbatimString(_) => parse_text(stringify!($type), value),
+                        _ => Err(FromRespError::new(stringify!($type), value)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_resp_float!(f32, f64);
+
+/// Converted from a Boolean, or an Integer `0` or `1` as replied in RESP2
+impl FromResp for bool {
+    fn from_resp(value: &RespType) -> Result<Self, FromRespError> {
+        let value = &*reply(value);
+        match value {
+            RespType::Boolean(boolean) => Ok(boolean.value()),
+            RespType::Integer(integer) => {
+                match integer.value() {
+                    0 => Ok(false),
+                    1 => Ok(true),
+                    _ => Err(FromRespError::new("bool", value)
+                        .with_value(Bytes::from(integer.raw_value()))),
+                }
+            }
+            _ => Err(FromRespError::new("bool", value)),
+        }
+    }
+}
+
+/// `None` for a Null, a null Bulk String or a null Array
+impl<T: FromResp> FromResp for Option<T> {
+    fn from_resp(value: &RespType) -> Result<Self, FromRespError> {
+        let value = &*reply(value);
+        match value {
+            RespType::Null(_) => Ok(None),
+            RespType::BulkString(bulk_string) if bulk_string.is_null() => Ok(None),
+            RespType::Array(array) if array.is_null() => Ok(None),
+            value => T::from_resp(value).map(Some),
+        }
+    }
+}
+
+/// Values of an Array, a Set or a Push, or keys and values of a Map
+fn values(value: &RespType) -> Option<ArrayIter> {
+    match value {
+        RespType::Array(array) if !array.is_null() => Some(array.iter()),
+        RespType::Set(set) => Some(ArrayIter::new(0x7e, set.bytes())),
+        RespType::Push(push) => Some(ArrayIter::new(0x3e, push.bytes())),
+        RespType::Map(map) => Some(ArrayIter::new(0x25, map.bytes())),
+        _ => None,
+    }
+}
+
+/// Converted from an Array, a Set or a Push
+impl<T: FromResp> FromResp for Vec<T> {
+    fn from_resp(value: &RespType) -> Result<Self, FromRespError> {
+        let value = &*reply(value);
+        match value {
+            RespType::Map(_) => Err(FromRespError::new("Vec", value)),
+            value => values(value)
+                .ok_or_else(|| FromRespError::new("Vec", value))?
+                .enumerate()
+                .map(|(index, value)| {
+                    T::from_resp(&value).map_err(|error| error.nested_in_path(Some(index)))
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Convert the keys of a Map, or of a flat Array of key/value pairs, and their values with `f`
+///
+/// `f` is given the index of each value, so errors can be nested at it.
+fn pairs<K, T, C>(
+    expected: &'static str,
+    value: &RespType,
+    mut f: impl FnMut(usize, RespType) -> Result<T, FromRespError>,
+) -> Result<C, FromRespError>
+where
+    K: FromResp,
+    C: FromIterator<(K, T)>,
+{
+    let value = &*reply(value);
+    let mut values = values(value)
+        .filter(|values| values.len() % 2 == 0)
+        .ok_or_else(|| FromRespError::new(expected, value))?
+        .enumerate();
+    let mut pairs = Vec::with_capacity(values.len() / 2);
+    while let (Some((index, key)), Some((_, value))) = (values.next(), values.next()) {
+        let key = K::from_resp(&key).map_err(|error| error.nested_in_path(Some(index)))?;
+        pairs.push((key, f(index + 1, value)?));
+    }
+    Ok(pairs.into_iter().collect())
+}
+
+/// Convert the keys and values of a Map, or of a flat Array of key/value pairs
+fn entries<K, V, C>(expected: &'static str, value: &RespType) -> Result<C, FromRespError>
+where
+    K: FromResp,
+    V: FromResp,
+    C: FromIterator<(K, V)>,
+{
+    pairs(expected, value, |index, value| {
+        V::from_resp(&value).map_err(|error| error.nested_in_path(Some(index)))
+    })
+}
+
+/// Fields of a struct held by a Map or a flat Array, each with the index of its value
+///
+/// Used by `#[derive(FromResp)]`.
+pub fn fields(
+    expected: &'static str,
+    value: &RespType,
+) -> Result<Vec<(usize, Bytes, RespType)>, FromRespError> {
+    let pairs: Vec<(Bytes, (usize, RespType))> =
+        pairs(expected, value, |index, value| Ok((index, value)))?;
+    Ok(pairs
+        .into_iter()
+        .map(|(key, (index, value))| (index, key, value))
+        .collect())
+}
+
+/// Exactly `count` values of an Array, a Set or a Push, for the fields of a tuple struct
+///
+/// Used by `#[derive(FromResp)]`.
+pub fn elements(
+    expected: &'static str,
+    count: usize,
+    value: &RespType,
+) -> Result<Vec<RespType>, FromRespError> {
+    let value = &*reply(value);
+    match value {
+        RespType::Map(_) => None,
+        value => values(value),
+    }
+    .filter(|values| values.len() == count)
+    .map(|values| values.collect())
+    .ok_or_else(|| FromRespError::new(expected, value))
+}
+
+/// Value of an absent field, `None` for an `Option`
+///
+/// Used by `#[derive(FromResp)]`.
+pub fn missing<T: FromResp>(expected: &'static str) -> Result<T, FromRespError> {
+    T::from_resp(&RespType::Null(Null::new())).map_err(|_| FromRespError::missing(expected))
+}
+
+/// Converted from a Map, or from an Array of keys and values such as `HGETALL` replies in RESP2
+impl<K, V, S> FromResp for HashMap<K, V, S>
+where
+    K: FromResp + Eq + Hash,
+    V: FromResp,
+    S: BuildHasher + Default,
+{
+    fn from_resp(value: &RespType) -> Result<Self, FromRespError> {
+        entries("HashMap", value)
+    }
+}
+
+/// Converted from a Map, or from an Array of keys and values such as `HGETALL` replies in RESP2
+impl<K, V> FromResp for BTreeMap<K, V>
+where
+    K: FromResp + Ord,
+    V: FromResp,
+{
+    fn from_resp(value: &RespType) -> Result<Self, FromRespError> {
+        entries("BTreeMap", value)
+    }
+}
+
+macro_rules! impl_from_resp_tuple {
+    ($count:literal; $($index:tt $type:ident),+) => {
+        /// Converted from an Array, a Set or a Push of exactly as many values
+        impl<$($type: FromResp),+> FromResp for ($($type,)+) {
+            fn from_resp(value: &RespType) -> Result<Self, FromRespError> {
+                const EXPECTED: &str = concat!("tuple of ", $count, " values");
+                let value = &*reply(value);
+                let mut values = match value {
+                    RespType::Map(_) => None,
+                    value => values(value),
+                }
+                .filter(|values| values.len() == $count)
+                .ok_or_else(|| FromRespError::new(EXPECTED, value))?;
+                Ok(($(
+                    $type::from_resp(&values.next().expect("tuple has as many values"))
+                        .map_err(|error| error.nested_in_path(Some($index)))?,
+                )+))
+            }
+        }
+    };
+}
+
+impl_from_resp_tuple!(1; 0 A);
+impl_from_resp_tuple!(2; 0 A, 1 B);
+impl_from_resp_tuple!(3; 0 A, 1 B, 2 C);
+impl_from_resp_tuple!(4; 0 A, 1 B, 2 C, 3 D);
+impl_from_resp_tuple!(5; 0 A, 1 B, 2 C, 3 D, 4 E);
+impl_from_resp_tuple!(6; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F);
+impl_from_resp_tuple!(7; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G);
+impl_from_resp_tuple!(8; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H);
+
+#[cfg(test)]
+mod tests_from_resp {
+    use crate::{
+        ArrayBuilder, AttributeBuilder, Boolean, BulkString, Double, Error, FromResp, Integer,
+        MapBuilder, Null, RespType, SimpleString, NULL_ARRAY, NULL_BULK_STRING,
+    };
+    use bytes::Bytes;
+    use std::collections::{BTreeMap, HashMap};
+
+    fn bulk_string(value: &str) -> RespType {
+        RespType::BulkString(BulkString::new(value.as_bytes()))
+    }
+
+    fn array(values: Vec<RespType>) -> RespType {
+        let mut array_builder = ArrayBuilder::new();
+        for value in values {
+            array_builder.insert(value);
+        }
+        RespType::Array(array_builder.build())
+    }
+
+    #[test]
+    fn test_strings() {
+        let value = RespType::SimpleString(SimpleString::new(b"OK"));
+        assert_eq!(String::from_resp(&value).unwrap(), "OK");
+        assert_eq!(Vec::<u8>::from_resp(&bulk_string("foo")).unwrap(), b"foo");
+        assert_eq!(
+            Bytes::from_resp(&bulk_string("foo")).unwrap(),
+            Bytes::from_static(b"foo")
+        );
+        let value = RespType::BulkString(BulkString::new(&[0xff]));
+        let error = String::from_resp(&value).unwrap_err();
+        assert_eq!(error.value(), Some(&[0xff][..]));
+        assert_eq!(
+            error.to_string(),
+            "Expected String, found BulkString \"\\xff\"."
+        );
+        let error = String::from_resp(&RespType::BulkString(NULL_BULK_STRING)).unwrap_err();
+        assert_eq!(error.found(), "null BulkString");
+    }
+
+    #[test]
+    fn test_numbers() {
+        let value = RespType::Integer(Integer::new(-42));
+        assert_eq!(i64::from_resp(&value).unwrap(), -42);
+        assert_eq!(f64::from_resp(&value).unwrap(), -42.0);
+        assert_eq!(String::from_resp(&value).unwrap(), "-42");
+        assert_eq!(u64::from_resp(&bulk_string("42")).unwrap(), 42);
+        let error = i8::from_resp(&RespType::Integer(Integer::new(256))).unwrap_err();
+        assert_eq!(error.to_string(), "Expected i8, found Integer \"256\".");
+        assert_eq!(f64::from_resp(&bulk_string("1.5")).unwrap(), 1.5);
+        assert_eq!(
+            f64::from_resp(&bulk_string("-inf")).unwrap(),
+            f64::NEG_INFINITY
+        );
+        let value = RespType::Double(Double::new(0.25));
+        assert_eq!(f32::from_resp(&value).unwrap(), 0.25);
+        assert_eq!(i64::from_resp(&value).unwrap_err().found(), "Double");
+    }
+
+    #[test]
+    fn test_bool() {
+        let value = RespType::Boolean(Boolean::new(true));
+        assert!(bool::from_resp(&value).unwrap());
+        assert!(!bool::from_resp(&RespType::Integer(Integer::new(0))).unwrap());
+        assert_eq!(
+            bool::from_resp(&RespType::Integer(Integer::new(2)))
+                .unwrap_err()
+                .value(),
+            Some(&b"2"[..])
+        );
+    }
+
+    #[test]
+    fn test_option() {
+        assert_eq!(
+            Option::<String>::from_resp(&RespType::Null(Null::new())),
+            Ok(None)
+        );
+        assert_eq!(
+            Option::<String>::from_resp(&RespType::BulkString(NULL_BULK_STRING)),
+            Ok(None)
+        );
+        assert_eq!(
+            Option::<Vec<String>>::from_resp(&RespType::Array(NULL_ARRAY)),
+            Ok(None)
+        );
+        assert_eq!(
+            Option::<String>::from_resp(&bulk_string("foo")),
+            Ok(Some("foo".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_vec_and_tuples() {
+        let value = array(vec![bulk_string("foo"), RespType::Integer(Integer::new(1))]);
+        assert_eq!(
+            Vec::<String>::from_resp(&value).unwrap(),
+            vec!["foo".to_owned(), "1".to_owned()]
+        );
+        assert_eq!(
+            <(String, i64)>::from_resp(&value).unwrap(),
+            ("foo".to_owned(), 1)
+        );
+        let error = <(String, i64, i64)>::from_resp(&value).unwrap_err();
+        assert_eq!(error.expected(), "tuple of 3 values");
+        assert_eq!(error.found(), "Array");
+        let error = <(i64, i64)>::from_resp(&value).unwrap_err();
+        assert_eq!(error.path(), &[0]);
+        let error = Vec::<String>::from_resp(&RespType::Array(NULL_ARRAY)).unwrap_err();
+        assert_eq!(error.to_string(), "Expected Vec, found null Array.");
+    }
+
+    #[test]
+    fn test_maps() {
+        let value = array(vec![
+            bulk_string("a"),
+            bulk_string("1"),
+            bulk_string("b"),
+            bulk_string("2"),
+        ]);
+        let hash = HashMap::<String, i64>::from_resp(&value).unwrap();
+        assert_eq!(hash.len(), 2);
+        assert_eq!(hash["b"], 2);
+        let value = RespType::Map(
+            MapBuilder::new()
+                .insert(bulk_string("a"), RespType::Integer(Integer::new(1)))
+                .insert(bulk_string("b"), bulk_string("x"))
+                .build(),
+        );
+        let error = BTreeMap::<String, i64>::from_resp(&value).unwrap_err();
+        assert_eq!(error.path(), &[3]);
+        assert_eq!(
+            error.to_string(),
+            "Expected i64, found BulkString \"x\" at [3]."
+        );
+        let value = array(vec![bulk_string("a")]);
+        assert_eq!(
+            HashMap::<String, String>::from_resp(&value)
+                .unwrap_err()
+                .expected(),
+            "HashMap"
+        );
+    }
+
+    #[test]
+    fn test_error_reply() {
+        let value = RespType::Error(Error::new(b"ERR unknown command"));
+        let error = String::from_resp(&value).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Expected String, found Error \"ERR unknown command\"."
+        );
+        let value = array(vec![array(vec![value])]);
+        let error = Vec::<Vec<String>>::from_resp(&value).unwrap_err();
+        assert_eq!(error.path(), &[0, 0]);
+    }
+
+    #[test]
+    fn test_attribute_reply() {
+        let ttl = (
+            RespType::SimpleString(SimpleString::new(b"ttl")),
+            RespType::Integer(Integer::new(3600)),
+        );
+        let attribute = |reply: RespType| {
+            RespType::Attribute(
+                AttributeBuilder::new()
+                    .insert(ttl.0.clone(), ttl.1.clone())
+                    .build(reply),
+            )
+        };
+        assert_eq!(
+            String::from_resp(&attribute(bulk_string("foo"))).unwrap(),
+            "foo"
+        );
+        assert_eq!(
+            i64::from_resp(&attribute(attribute(RespType::Integer(Integer::new(1))))),
+            Ok(1)
+        );
+        assert_eq!(
+            Option::<String>::from_resp(&attribute(RespType::Null(Null::new()))),
+            Ok(None)
+        );
+        let value = attribute(array(vec![bulk_string("1"), bulk_string("2")]));
+        assert_eq!(Vec::<i64>::from_resp(&value).unwrap(), vec![1, 2]);
+        assert_eq!(<(i64, i64)>::from_resp(&value).unwrap(), (1, 2));
+        let value = array(vec![attribute(bulk_string("x"))]);
+        let error = Vec::<i64>::from_resp(&value).unwrap_err();
+        assert_eq!(error.found(), "BulkString");
+        assert_eq!(error.path(), &[0]);
+    }
+}
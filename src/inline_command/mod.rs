@@ -0,0 +1,326 @@
+use crate::{Array, ArrayBuilder, BulkString, Limits, RespError, RespErrorKind, RespType};
+use bytes::Bytes;
+
+/// Inline command, arguments separated by spaces on a single line, as typed in telnet
+///
+/// Arguments follow the quoting rules of Redis. Within double quotes, `\n`, `\r`, `\t`, `\b`,
+/// `\a` and `\xHH` hex escapes are unescaped and any other escaped byte is kept as is.
+/// Within single quotes, only `\'` is unescaped. A closing quote must be followed by a space
+/// or the end of the line.
+///
+/// # Example
+/// ``` rust
+/// use resp_protocol::InlineCommand;
+///
+/// let string = "SET key \"hello world\\x21\"\r\n";
+/// let command = InlineCommand::parse(string.as_bytes(), &mut 0, &string.len()).unwrap();
+/// assert_eq!(command.args(), vec![&b"SET"[..], b"key", b"hello world!"]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct InlineCommand(Bytes);
+
+impl InlineCommand {
+    #[inline]
+    pub fn bytes(&self) -> Bytes {
+        self.0.clone()
+    }
+
+    #[allow(clippy::len_without_is_empty)]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Unquoted arguments, none for an empty line
+    pub fn args(&self) -> Vec<Bytes> {
+        let mut args = Vec::new();
+        split_args(line(&self.0), |arg| args.push(Bytes::copy_from_slice(arg)))
+            .expect("InlineCommand holds balanced quotes");
+        args
+    }
+
+    /// Arguments as an Array of Bulk Strings, as sent by RESP clients
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{ArrayBuilder, BulkString, InlineCommand, RespType};
+    ///
+    /// let string = "PING\r\n";
+    /// let command = InlineCommand::parse(string.as_bytes(), &mut 0, &string.len()).unwrap();
+    /// let array = ArrayBuilder::new()
+    ///     .insert(RespType::BulkString(BulkString::new(b"PING")))
+    ///     .build();
+    /// assert_eq!(command.to_array(), array);
+    /// ```
+    pub fn to_array(&self) -> Array {
+        let mut array_builder = ArrayBuilder::new();
+        split_args(line(&self.0), |arg| {
+            array_builder.insert(RespType::BulkString(BulkString::new(arg)));
+        })
+        .expect("InlineCommand holds balanced quotes");
+        array_builder.build()
+    }
+
+    #[inline]
+    pub fn from_bytes_unchecked(input: Bytes) -> Self {
+        Self(input)
+    }
+
+    #[inline]
+    pub fn from_slice_unchecked(input: &[u8]) -> Self {
+        let bytes = Bytes::copy_from_slice(input);
+        Self::from_bytes_unchecked(bytes)
+    }
+
+    pub fn while_valid(input: &[u8], start: &mut usize, end: &usize) -> Result<(), RespError> {
+        Self::while_valid_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Validate an inline command, ending with `\n` or `\r\n`, rejecting input beyond `limits`
+    pub fn while_valid_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<(), RespError> {
+        limits.while_valid_frame(input, start, end, |input, start, end| {
+            let mut index = *start;
+            while index < *end && input[index] != 0x0a {
+                index += 1;
+            }
+            limits.check_inline_length(*start, index, input, end)?;
+            if index >= *end {
+                return Err(RespError::incomplete(None, input, end));
+            }
+            split_args(line(&input[*start..index + 1]), |_| {}).map_err(|offset| {
+                RespError::new(RespErrorKind::UnbalancedQuotes, input, end, *start + offset)
+            })?;
+            *start = index + 1;
+            Ok(())
+        })
+    }
+
+    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, RespError> {
+        Self::parse_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Parse an inline command, rejecting input beyond `limits`
+    pub fn parse_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        Self::while_valid_with(input, &mut index, end, limits)?;
+        let value = Self::from_slice_unchecked(&input[*start..index]);
+        *start = index;
+        Ok(value)
+    }
+}
+
+/// Line of an inline command, without its `\n` or `\r\n`
+#[inline]
+fn line(input: &[u8]) -> &[u8] {
+    let line = &input[..input.len() - 1];
+    match line.last() {
+        Some(0x0d) => &line[..line.len() - 1],
+        _ => line,
+    }
+}
+
+/// Whitespace separating arguments, as matched by `isspace`
+#[inline]
+fn is_space(byte: u8) -> bool {
+    matches!(byte, 0x20 | 0x09 | 0x0a | 0x0b | 0x0c | 0x0d)
+}
+
+#[inline]
+fn hex_digit(byte: Option<&u8>) -> Option<u8> {
+    match byte {
+        Some(byte @ 0x30..=0x39) => Some(byte - 0x30),
+        Some(byte @ 0x61..=0x66) => Some(byte - 0x61 + 10),
+        Some(byte @ 0x41..=0x46) => Some(byte - 0x41 + 10),
+        _ => None,
+    }
+}
+
+/// Split `line` into unquoted arguments, calling `f` with each of them
+///
+/// Returns the offset of the first byte breaking the quoting rules.
+fn split_args<F>(line: &[u8], mut f: F) -> Result<(), usize>
+where
+    F: FnMut(&[u8]),
+{
+    let mut arg = Vec::new();
+    let mut index = 0;
+    loop {
+        while index < line.len() && is_space(line[index]) {
+            index += 1;
+        }
+        if index >= line.len() {
+            return Ok(());
+        }
+        arg.clear();
+        let mut quote = None;
+        loop {
+            match quote {
+                Some(0x22) => {
+                    // Within double quotes
+                    if index >= line.len() {
+                        return Err(index);
+                    }
+                    match line[index] {
+                        0x5c => match (
+                            line.get(index + 1),
+                            hex_digit(line.get(index + 2)),
+                            hex_digit(line.get(index + 3)),
+                        ) {
+                            (Some(0x78), Some(high), Some(low)) => {
+                                arg.push((high << 4) | low);
+                                index += 4;
+                            }
+                            (Some(byte), _, _) => {
+                                arg.push(match byte {
+                                    0x6e => 0x0a, // "n"
+                                    0x72 => 0x0d, // "r"
+                                    0x74 => 0x09, // "t"
+                                    0x62 => 0x08, // "b"
+                                    0x61 => 0x07, // "a"
+                                    byte => *byte,
+                                });
+                                index += 2;
+                            }
+                            (None, _, _) => {
+                                arg.push(0x5c);
+                                index += 1;
+                            }
+                        },
+                        0x22 => {
+                            index += 1;
+                            if index < line.len() && !is_space(line[index]) {
+                                return Err(index);
+                            }
+                            break;
+                        }
+                        byte => {
+                            arg.push(byte);
+                            index += 1;
+                        }
+                    }
+                }
+                Some(_) => {
+                    // Within single quotes
+                    if index >= line.len() {
+                        return Err(index);
+                    }
+                    match line[index] {
+                        0x5c if line.get(index + 1) == Some(&0x27) => {
+                            arg.push(0x27);
+                            index += 2;
+                        }
+                        0x27 => {
+                            index += 1;
+                            if index < line.len() && !is_space(line[index]) {
+                                return Err(index);
+                            }
+                            break;
+                        }
+                        byte => {
+                            arg.push(byte);
+                            index += 1;
+                        }
+                    }
+                }
+                None => {
+                    if index >= line.len() {
+                        break;
+                    }
+                    match line[index] {
+                        0x20 | 0x0a | 0x0d | 0x09 | 0x00 => {
+                            index += 1;
+                            break;
+                        }
+                        byte @ (0x22 | 0x27) => {
+                            quote = Some(byte);
+                            index += 1;
+                        }
+                        byte => {
+                            arg.push(byte);
+                            index += 1;
+                        }
+                    }
+                }
+            }
+        }
+        f(&arg);
+    }
+}
+
+#[cfg(test)]
+mod tests_inline_command {
+    use crate::{InlineCommand, Limits, RespErrorKind};
+    use bytes::Bytes;
+
+    fn args(string: &str) -> Vec<Bytes> {
+        InlineCommand::parse(string.as_bytes(), &mut 0, &string.len())
+            .unwrap()
+            .args()
+    }
+
+    #[test]
+    fn test_parse() {
+        let string = "PING\r\nECHO foo\n";
+        let mut cursor = 0;
+        let command = InlineCommand::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        assert_eq!(command.bytes(), Bytes::from_static(b"PING\r\n"));
+        assert_eq!(command.args(), vec![&b"PING"[..]]);
+        let command = InlineCommand::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        assert_eq!(command.args(), vec![&b"ECHO"[..], b"foo"]);
+        assert_eq!(cursor, string.len());
+    }
+
+    #[test]
+    fn test_args() {
+        assert_eq!(
+            args("  SET \t key   value \r\n"),
+            vec![&b"SET"[..], b"key", b"value"]
+        );
+        assert_eq!(args("\r\n"), Vec::<Bytes>::new());
+        assert_eq!(args("SET k \"\"\r\n"), vec![&b"SET"[..], b"k", b""]);
+        assert_eq!(
+            args("SET k \"a\\\"b\\n\\x41\\xZZ\\q\"\r\n"),
+            vec![&b"SET"[..], b"k", b"a\"b\nAxZZq"]
+        );
+        assert_eq!(
+            args("SET k 'it\\'s \"raw\\n\"'\r\n"),
+            vec![&b"SET"[..], b"k", b"it's \"raw\\n\""]
+        );
+        assert_eq!(args("SET k\"a b\"\r\n"), vec![&b"SET"[..], b"ka b"]);
+    }
+
+    #[test]
+    fn test_while_valid_unbalanced_quotes() {
+        for (string, offset) in [("SET k \"foo\r\n", 10), ("SET k 'foo'bar\r\n", 11)] {
+            let error =
+                InlineCommand::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err();
+            assert_eq!(error, RespErrorKind::UnbalancedQuotes, "{:?}", string);
+            assert_eq!(error.offset(), offset, "{:?}", string);
+        }
+    }
+
+    #[test]
+    fn test_while_valid_incomplete() {
+        let string = "SET key";
+        assert_eq!(
+            InlineCommand::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: None }
+        );
+        let limits = Limits::new().with_max_inline_length(4);
+        assert_eq!(
+            InlineCommand::while_valid_with(string.as_bytes(), &mut 0, &string.len(), &limits)
+                .unwrap_err(),
+            RespErrorKind::MaxInlineLengthExceeded
+        );
+    }
+}
@@ -1,8 +1,9 @@
-use crate::RespError;
-use bytes::{Buf, BufMut, Bytes, BytesMut};
+use crate::{Limits, RespError, RespErrorKind};
+use bytes::{BufMut, Bytes, BytesMut};
+use std::convert::TryFrom;
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct Integer(Bytes);
+pub struct Integer(pub(crate) Bytes);
 
 impl Integer {
     #[inline]
@@ -13,19 +14,34 @@ impl Integer {
         bytes.put_slice(string.as_bytes());
         bytes.put_u8(0x0d); // CR
         bytes.put_u8(0x0a); // LF
-        Self::from_bytes(bytes.freeze())
+        Self::from_bytes_unchecked(bytes.freeze())
+    }
+
+    /// Value of the Integer
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Integer was built from bytes that do not hold a valid value.
+    ///
+    /// # Example
+    /// ```
+    /// use resp_protocol::Integer;
+    ///
+    /// let integer = Integer::new(-100);
+    /// assert_eq!(integer.value(), -100);
+    /// ```
+    #[inline]
+    pub fn value(&self) -> i64 {
+        let length = self.0.len();
+        unsafe { std::str::from_utf8_unchecked(&self.0[1..(length - 2)]) }
+            .parse::<i64>()
+            .expect("Integer holds a valid value")
     }
 
     #[inline]
     pub fn raw_value(&self) -> Vec<u8> {
         let length = self.0.len();
-        let mut bytes = self.0.slice(1..(length - 2));
-        let mut vector = Vec::<u8>::with_capacity(length - 3);
-        unsafe {
-            vector.set_len(length - 3);
-        }
-        bytes.copy_to_slice(vector.as_mut_slice());
-        vector
+        self.0.slice(1..(length - 2)).to_vec()
     }
 
     #[inline]
@@ -33,69 +49,234 @@ impl Integer {
         self.0.clone()
     }
 
+    #[allow(clippy::len_without_is_empty)]
     #[inline]
     pub fn len(&self) -> usize {
         self.0.len()
     }
 
+    /// Validate a value against `[-]<digits>` fitting in an `i64`
     pub fn validate_value(input: &[u8]) -> Result<(), RespError> {
-        let mut index = 0;
-        let length = input.len();
-        while index < length && input[index] != 0x0a && input[index] != 0x0d {
-            index += 1;
-        }
-        if index != length {
-            return Err(RespError::InvalidValue);
+        let digits_start_index = match input.first() {
+            Some(0x2d) => 1,
+            _ => 0,
+        };
+        let invalid_index = match input[digits_start_index..]
+            .iter()
+            .position(|byte| !byte.is_ascii_digit())
+        {
+            Some(position) => Some(digits_start_index + position),
+            None if input.len() == digits_start_index => Some(digits_start_index),
+            None => None,
+        };
+        if let Some(index) = invalid_index {
+            return Err(
+                RespError::new(RespErrorKind::InvalidInteger, input, &input.len(), index)
+                    .expecting("digit"),
+            );
         }
+        unsafe { std::str::from_utf8_unchecked(input) }
+            .parse::<i64>()
+            .map_err(|_| RespError::new(RespErrorKind::InvalidInteger, input, &input.len(), 0))?;
         Ok(())
     }
 
     #[inline]
-    pub fn from_bytes(input: Bytes) -> Self {
+    pub fn from_bytes_unchecked(input: Bytes) -> Self {
         Self(input)
     }
 
     #[inline]
-    pub fn from_slice(input: &[u8]) -> Self {
+    pub fn from_slice_unchecked(input: &[u8]) -> Self {
         let bytes = Bytes::copy_from_slice(input);
-        Self::from_bytes(bytes)
+        Self::from_bytes_unchecked(bytes)
+    }
+
+    #[deprecated(since = "0.0.11", note = "use from_bytes_unchecked")]
+    #[inline]
+    pub fn from_bytes(input: Bytes) -> Self {
+        Self::from_bytes_unchecked(input)
+    }
+
+    #[deprecated(since = "0.0.11", note = "use from_slice_unchecked")]
+    #[inline]
+    pub fn from_slice(input: &[u8]) -> Self {
+        Self::from_slice_unchecked(input)
+    }
+
+    /// Build a new Integer from `input`, checking that it holds exactly one valid value
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{Integer, RespErrorKind};
+    /// use bytes::Bytes;
+    ///
+    /// assert!(Integer::try_from_bytes(Bytes::from_static(b":100\r\n")).is_ok());
+    /// assert_eq!(
+    ///     Integer::try_from_bytes(Bytes::from_static(b":100\r\n\r\n")).unwrap_err(),
+    ///     RespErrorKind::TrailingBytes
+    /// );
+    /// ```
+    pub fn try_from_bytes(input: Bytes) -> Result<Self, RespError> {
+        let mut index = 0;
+        Self::while_valid(&input, &mut index, &input.len())?;
+        if index != input.len() {
+            return Err(RespError::new(
+                RespErrorKind::TrailingBytes,
+                &input,
+                &input.len(),
+                index,
+            ));
+        }
+        Ok(Self::from_bytes_unchecked(input))
     }
 
+    #[inline]
+    pub fn try_from_slice(input: &[u8]) -> Result<Self, RespError> {
+        Self::try_from_bytes(Bytes::copy_from_slice(input))
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated by the global allocator for exactly `length` bytes,
+    /// as required by [`Vec::from_raw_parts`].
+    #[deprecated(
+        since = "0.0.11",
+        note = "use `try_from` with an owned `Vec<u8>`, `String`, `Box<[u8]>` or `BytesMut`"
+    )]
     #[inline]
     pub unsafe fn from_raw(ptr: *mut u8, length: usize) -> Self {
         let vector = Vec::from_raw_parts(ptr, length, length);
         let bytes = Bytes::from(vector);
-        Self::from_bytes(bytes)
+        Self::from_bytes_unchecked(bytes)
     }
 
     pub fn while_valid(input: &[u8], start: &mut usize, end: &usize) -> Result<(), RespError> {
+        Self::while_valid_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Validate an Integer, whose digits may not be longer than [`Limits::max_inline_length`]
+    pub fn while_valid_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<(), RespError> {
+        limits.while_valid_frame(input, start, end, |input, start, end| {
+            Self::while_valid_nested(input, start, end, limits)
+        })
+    }
+
+    pub(crate) fn while_valid_nested(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<(), RespError> {
         let mut index = *start;
-        if index >= *end || input[index] != 0x3a {
-            return Err(RespError::InvalidFirstChar);
+        if index >= *end {
+            return Err(RespError::incomplete(None, input, end).within(0x3a));
+        }
+        if input[index] != 0x3a {
+            return Err(
+                RespError::new(RespErrorKind::InvalidFirstChar, input, end, index)
+                    .expecting(":")
+                    .within(0x3a),
+            );
         }
         index += 1;
-        while index < *end && input[index] != 0x0d && input[index] != 0x0a {
+        if index < *end && input[index] == 0x2d {
+            index += 1;
+        }
+        while index < *end && input[index].is_ascii_digit() {
             index += 1;
         }
-        if index + 1 >= *end || input[index] != 0x0d || input[index + 1] != 0x0a {
-            return Err(RespError::InvalidTerminate);
+        limits
+            .check_inline_length(*start + 1, index, input, end)
+            .map_err(|error| error.within(0x3a))?;
+        if index >= *end {
+            return Err(RespError::incomplete(None, input, end).within(0x3a));
+        }
+        if input[index] == 0x0a {
+            return Err(
+                RespError::new(RespErrorKind::InvalidTerminate, input, end, index)
+                    .expecting("\r")
+                    .within(0x3a),
+            );
+        }
+        if input[index] != 0x0d {
+            return Err(
+                RespError::new(RespErrorKind::InvalidInteger, input, end, index)
+                    .expecting("digit")
+                    .within(0x3a),
+            );
+        }
+        Self::validate_value(&input[*start + 1..index])
+            .map_err(|error| error.rebase(input, end, *start + 1).within(0x3a))?;
+        if index + 1 >= *end {
+            return Err(RespError::incomplete(Some(1), input, end).within(0x3a));
+        }
+        if input[index + 1] != 0x0a {
+            return Err(
+                RespError::new(RespErrorKind::InvalidTerminate, input, end, index + 1)
+                    .expecting("\n")
+                    .within(0x3a),
+            );
         }
         *start = index + 2;
         Ok(())
     }
 
     pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, RespError> {
+        Self::parse_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Parse an Integer, whose digits may not be longer than [`Limits::max_inline_length`]
+    pub fn parse_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        Self::while_valid_with(input, &mut index, end, limits)?;
+        let value = Self::from_slice_unchecked(&input[*start..index]);
+        *start = index;
+        Ok(value)
+    }
+
+    /// Parse an Integer from `input`, sharing its buffer instead of copying it
+    pub fn parse_bytes(input: &Bytes, start: &mut usize) -> Result<Self, RespError> {
+        Self::parse_bytes_with(input, start, &Limits::unlimited())
+    }
+
+    /// Parse an Integer from `input` like [`Integer::parse_bytes`], whose digits may not be longer
+    /// than [`Limits::max_inline_length`]
+    pub fn parse_bytes_with(
+        input: &Bytes,
+        start: &mut usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
         let mut index = *start;
-        Self::while_valid(input, &mut index, end)?;
-        let value = Self::from_slice(&input[*start..index]);
+        Self::while_valid_with(input, &mut index, &input.len(), limits)?;
+        let value = Self::from_bytes_unchecked(input.slice(*start..index));
         *start = index;
         Ok(value)
     }
 }
 
+impl TryFrom<Bytes> for Integer {
+    type Error = RespError;
+
+    fn try_from(input: Bytes) -> Result<Self, RespError> {
+        Self::try_from_bytes(input)
+    }
+}
+
 #[cfg(test)]
 mod tests_integer {
     use crate::integer::Integer;
+    use crate::RespErrorKind;
     use bytes::Bytes;
 
     #[test]
@@ -125,12 +306,53 @@ mod tests_integer {
     }
 
     #[test]
-    #[should_panic(expected = "InvalidValue")]
+    #[should_panic(expected = "InvalidInteger")]
     fn test_validate_invalid_value() {
         let value = b"100\r\n";
         assert_eq!(Integer::validate_value(value).unwrap(), ())
     }
 
+    #[test]
+    fn test_value() {
+        let integer = Integer(Bytes::from_static(b":-100\r\n"));
+        assert_eq!(integer.value(), -100);
+        assert_eq!(Integer::new(i64::MIN).value(), i64::MIN);
+        assert_eq!(Integer::new(i64::MAX).value(), i64::MAX);
+    }
+
+    #[test]
+    fn test_while_valid_invalid_integer() {
+        for string in [
+            ":abc\r\n",
+            ":+1\r\n",
+            ":\r\n",
+            ":-\r\n",
+            ":1.5\r\n",
+            ":1 \r\n",
+            ":9223372036854775808\r\n",
+            ":-9223372036854775809\r\n",
+            ":99999999999999999999\r\n",
+        ] {
+            assert_eq!(
+                Integer::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+                RespErrorKind::InvalidInteger,
+                "{:?}",
+                string
+            );
+        }
+    }
+
+    #[test]
+    fn test_while_valid_bounds() {
+        let string = ":-9223372036854775808\r\n";
+        let mut cursor = 0;
+        assert_eq!(
+            Integer::while_valid(string.as_bytes(), &mut cursor, &string.len()),
+            Ok(())
+        );
+        assert_eq!(cursor, string.len());
+    }
+
     #[test]
     fn test_parse() {
         let string = ":100\r\n+bar\r\n";
@@ -142,4 +364,32 @@ mod tests_integer {
         );
         assert_eq!(cursor, 6);
     }
+
+    #[test]
+    fn test_while_valid_incomplete() {
+        let string = ":10";
+        assert_eq!(
+            Integer::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: None }
+        );
+        let string = ":100\r";
+        assert_eq!(
+            Integer::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: Some(1) }
+        );
+    }
+
+    #[test]
+    fn test_while_valid_error_offset() {
+        let string = "+OK\r\n:-12a\r\n";
+        let error = Integer::while_valid(string.as_bytes(), &mut 5, &string.len()).unwrap_err();
+        assert_eq!(error.offset(), 9);
+        assert_eq!(error.byte(), Some(b'a'));
+        let string = ":-\r\n";
+        let error = Integer::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err();
+        assert_eq!(error, RespErrorKind::InvalidInteger);
+        assert_eq!(error.offset(), 2);
+        assert_eq!(error.byte(), Some(0x0d));
+        assert_eq!(error.type_name(), Some("Integer"));
+    }
 }
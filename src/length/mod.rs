@@ -0,0 +1,137 @@
+use crate::{RespError, RespErrorKind};
+
+/// Validate a decimal length terminated by CR, leaving `start` on the CR
+pub(crate) fn while_valid(
+    input: &[u8],
+    start: &mut usize,
+    end: &usize,
+) -> Result<usize, RespError> {
+    let length_start_index = *start;
+    let mut index = length_start_index;
+    while index < *end && input[index] >= 0x30 && input[index] <= 0x39 {
+        index += 1;
+    }
+    if index - length_start_index > 1 && input[length_start_index] == 0x30 {
+        return Err(RespError::new(
+            RespErrorKind::InvalidLength,
+            input,
+            end,
+            length_start_index,
+        ));
+    }
+    if index >= *end {
+        return Err(RespError::incomplete(None, input, end));
+    }
+    if index == length_start_index {
+        return Err(
+            RespError::new(RespErrorKind::InvalidLength, input, end, index).expecting("digit"),
+        );
+    }
+    let length = unsafe { std::str::from_utf8_unchecked(&input[length_start_index..index]) }
+        .parse::<usize>()
+        .map_err(|_| {
+            RespError::new(RespErrorKind::InvalidLength, input, end, length_start_index)
+        })?;
+    if input[index] != 0x0d {
+        return Err(
+            RespError::new(RespErrorKind::InvalidLengthSeparator, input, end, index)
+                .expecting("\r"),
+        );
+    }
+    *start = index;
+    Ok(length)
+}
+
+/// Validate a fixed token, such as the `-1\r\n` of a null value, reporting `kind` on mismatch
+pub(crate) fn while_valid_token(
+    token: &'static [u8],
+    kind: RespErrorKind,
+    input: &[u8],
+    start: &mut usize,
+    end: &usize,
+) -> Result<(), RespError> {
+    let index = *start;
+    let available = (*end - index).min(token.len());
+    if let Some(position) =
+        (0..available).find(|&position| input[index + position] != token[position])
+    {
+        let expected = std::str::from_utf8(&token[position..]).expect("tokens are ASCII");
+        return Err(RespError::new(kind, input, end, index + position).expecting(expected));
+    }
+    if available < token.len() {
+        return Err(RespError::incomplete(
+            Some(token.len() - available),
+            input,
+            end,
+        ));
+    }
+    *start = index + token.len();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests_length {
+    use crate::length::{while_valid, while_valid_token};
+    use crate::RespErrorKind;
+
+    #[test]
+    fn test_while_valid() {
+        let mut cursor = 0;
+        assert_eq!(while_valid(b"120\r\n", &mut cursor, &5), Ok(120));
+        assert_eq!(cursor, 3);
+        assert_eq!(
+            while_valid(b"12", &mut 0, &2).unwrap_err(),
+            RespErrorKind::Incomplete { needed: None }
+        );
+        assert_eq!(
+            while_valid(b"012\r\n", &mut 0, &5).unwrap_err(),
+            RespErrorKind::InvalidLength
+        );
+        assert_eq!(
+            while_valid(b"\r\n", &mut 0, &2).unwrap_err(),
+            RespErrorKind::InvalidLength
+        );
+        assert_eq!(
+            while_valid(b"1\n", &mut 0, &2).unwrap_err(),
+            RespErrorKind::InvalidLengthSeparator
+        );
+    }
+
+    #[test]
+    fn test_while_valid_token() {
+        let mut cursor = 0;
+        assert_eq!(
+            while_valid_token(
+                b".\r\n",
+                RespErrorKind::InvalidTerminate,
+                b".\r\n",
+                &mut cursor,
+                &3
+            ),
+            Ok(())
+        );
+        assert_eq!(cursor, 3);
+        assert_eq!(
+            while_valid_token(
+                b".\r\n",
+                RespErrorKind::InvalidTerminate,
+                b".\r",
+                &mut 0,
+                &2
+            )
+            .unwrap_err(),
+            RespErrorKind::Incomplete { needed: Some(1) }
+        );
+        assert_eq!(
+            while_valid_token(
+                b".\r\n",
+                RespErrorKind::InvalidTerminate,
+                b".\n",
+                &mut 0,
+                &2
+            )
+            .unwrap_err(),
+            RespErrorKind::InvalidTerminate
+        );
+    }
+}
@@ -1,85 +1,744 @@
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
+use std::convert::TryFrom;
 
+// Lets code generated by `resp-protocol-derive` name this crate from within it.
+extern crate self as resp_protocol;
+
+mod aggregate;
 mod array;
+mod attribute;
+mod big_number;
+mod blob;
+mod blob_error;
+mod boolean;
 mod bulk_string;
+#[cfg(feature = "tokio-codec")]
+mod codec;
+mod command;
+#[cfg(feature = "serde")]
+pub mod de;
+mod decoder;
+mod double;
 mod error;
+mod from_resp;
+mod inline_command;
 mod integer;
+mod length;
+mod limits;
+mod map;
+mod null;
+mod protocol;
+mod push;
+mod request;
+mod resp_error;
+mod resp_ref;
+#[cfg(feature = "serde")]
+pub mod ser;
+mod set;
 mod simple_string;
+mod streamed;
+mod to_resp;
+mod verbatim_string;
 
-pub use array::{Array, ArrayBuilder, EMPTY_ARRAY, NULL_ARRAY};
+pub use array::{Array, ArrayBuilder, ArrayIter, EMPTY_ARRAY, NULL_ARRAY};
+pub use attribute::{Attribute, AttributeBuilder};
+pub use big_number::BigNumber;
+pub use blob_error::BlobError;
+pub use boolean::Boolean;
 pub use bulk_string::{BulkString, EMPTY_BULK_STRING, NULL_BULK_STRING};
+#[cfg(feature = "tokio-codec")]
+pub use codec::{RespCodec, RespCodecError};
+pub use command::{Command, ToRespArg};
+pub use decoder::Decoder;
+pub use double::Double;
 pub use error::Error;
+pub use from_resp::{FromResp, FromRespError};
+pub use inline_command::InlineCommand;
 pub use integer::Integer;
+pub use limits::Limits;
+pub use map::{Map, MapBuilder, EMPTY_MAP};
+pub use null::Null;
+pub use protocol::Protocol;
+pub use push::{Push, PushBuilder};
+pub use request::Request;
+pub use resp_error::{RespError, RespErrorKind};
+pub use resp_ref::{
+    ArrayRef, ArrayRefIter, AttributeRef, BigNumberRef, BlobErrorRef, BooleanRef, BulkStringRef,
+    DoubleRef, ErrorRef, IntegerRef, MapRef, MapRefIter, NullRef, PushRef, RespRef, SetRef,
+    SimpleStringRef, VerbatimStringRef,
+};
+pub use set::{Set, SetBuilder, EMPTY_SET};
 pub use simple_string::SimpleString;
+pub use streamed::Streamed;
+pub use to_resp::ToResp;
+pub use verbatim_string::VerbatimString;
 
-#[derive(Debug, Clone)]
-pub enum RespError {
-    InvalidFirstChar,
-    InvalidLength,
-    InvalidLengthSeparator,
-    InvalidNullValue,
-    InvalidValue,
-    InvalidTerminate,
-    LengthsNotMatch,
-}
+#[cfg(feature = "derive")]
+pub use resp_protocol_derive::{FromResp, ToResp};
 
-impl std::fmt::Display for RespError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        match self {
-            RespError::InvalidFirstChar => {
-                write!(f, "Invalid first char.")
-            }
-            RespError::InvalidLength => {
-                write!(f, "Invalid length.")
-            }
-            RespError::InvalidLengthSeparator => {
-                write!(f, "Invalid length separator.")
-            }
-            RespError::InvalidValue => {
-                write!(f, "Invalid value.")
-            }
-            RespError::InvalidNullValue => {
-                write!(f, "Invalid null value.")
-            }
-            RespError::LengthsNotMatch => {
-                write!(f, "Lengths do not match.")
-            }
-            RespError::InvalidTerminate => {
-                write!(f, "Invalid terminate.")
-            }
-        }
-    }
+/// Helpers for the code generated by `resp-protocol-derive`, not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::from_resp::{elements, fields, missing};
+    pub use crate::to_resp::{array, map};
 }
 
-impl std::error::Error for RespError {}
-
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum RespType {
     SimpleString(SimpleString),
     Error(Error),
     Integer(Integer),
     BulkString(BulkString),
     Array(Array),
+    Null(Null),
+    Boolean(Boolean),
+    Double(Double),
+    BigNumber(BigNumber),
+    Map(Map),
+    Set(Set),
+    Push(Push),
+    Attribute(Attribute),
+    BlobError(BlobError),
+    VerbatimString(VerbatimString),
 }
 
 impl RespType {
-    fn len(&self) -> usize {
+    #[allow(clippy::len_without_is_empty)]
+    #[inline]
+    pub fn len(&self) -> usize {
         match self {
             RespType::SimpleString(simple_string) => simple_string.len(),
             RespType::Error(error) => error.len(),
             RespType::Integer(integer) => integer.len(),
             RespType::BulkString(bulk_string) => bulk_string.len(),
             RespType::Array(array) => array.len(),
+            RespType::Null(null) => null.len(),
+            RespType::Boolean(boolean) => boolean.len(),
+            RespType::Double(double) => double.len(),
+            RespType::BigNumber(big_number) => big_number.len(),
+            RespType::Map(map) => map.len(),
+            RespType::Set(set) => set.len(),
+            RespType::Push(push) => push.len(),
+            RespType::Attribute(attribute) => attribute.len(),
+            RespType::BlobError(blob_error) => blob_error.len(),
+            RespType::VerbatimString(verbatim_string) => verbatim_string.len(),
         }
     }
 
-    fn bytes(&self) -> Bytes {
+    #[inline]
+    pub fn bytes(&self) -> Bytes {
         match self {
             RespType::SimpleString(simple_string) => simple_string.bytes(),
             RespType::Error(error) => error.bytes(),
             RespType::Integer(integer) => integer.bytes(),
             RespType::BulkString(bulk_string) => bulk_string.bytes(),
             RespType::Array(array) => array.bytes(),
+            RespType::Null(null) => null.bytes(),
+            RespType::Boolean(boolean) => boolean.bytes(),
+            RespType::Double(double) => double.bytes(),
+            RespType::BigNumber(big_number) => big_number.bytes(),
+            RespType::Map(map) => map.bytes(),
+            RespType::Set(set) => set.bytes(),
+            RespType::Push(push) => push.bytes(),
+            RespType::Attribute(attribute) => attribute.bytes(),
+            RespType::BlobError(blob_error) => blob_error.bytes(),
+            RespType::VerbatimString(verbatim_string) => verbatim_string.bytes(),
+        }
+    }
+
+    /// Borrowed view of the value, over its bytes
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{RespRef, RespType, SimpleString};
+    ///
+    /// let value = RespType::SimpleString(SimpleString::new(b"OK"));
+    /// assert!(matches!(
+    ///     value.as_resp_ref(),
+    ///     RespRef::SimpleString(simple_string) if simple_string.value() == b"OK"
+    /// ));
+    /// ```
+    pub fn as_resp_ref(&self) -> RespRef<'_> {
+        let input = match self {
+            RespType::SimpleString(simple_string) => &simple_string.0,
+            RespType::Error(error) => &error.0,
+            RespType::Integer(integer) => &integer.0,
+            RespType::BulkString(bulk_string) => &bulk_string.0,
+            RespType::Array(array) => &array.0,
+            RespType::Null(null) => &null.0,
+            RespType::Boolean(boolean) => &boolean.0,
+            RespType::Double(double) => &double.0,
+            RespType::BigNumber(big_number) => &big_number.0,
+            RespType::Map(map) => &map.0,
+            RespType::Set(set) => &set.0,
+            RespType::Push(push) => &push.0,
+            RespType::Attribute(attribute) => &attribute.0,
+            RespType::BlobError(blob_error) => &blob_error.0,
+            RespType::VerbatimString(verbatim_string) => &verbatim_string.0,
+        };
+        RespRef::from_slice_unchecked(input)
+    }
+
+    /// Wrap a single, already validated value, dispatching on its type byte
+    ///
+    /// # Panics
+    ///
+    /// Panics if `input` is empty or starts with an unknown type byte.
+    pub fn from_bytes_unchecked(input: Bytes) -> Self {
+        match input[0] {
+            0x2b => RespType::SimpleString(SimpleString::from_bytes_unchecked(input)),
+            0x2d => RespType::Error(Error::from_bytes_unchecked(input)),
+            0x3a => RespType::Integer(Integer::from_bytes_unchecked(input)),
+            0x24 => RespType::BulkString(BulkString::from_bytes_unchecked(input)),
+            0x2a => RespType::Array(Array::from_bytes_unchecked(input)),
+            0x5f => RespType::Null(Null::from_bytes_unchecked(input)),
+            0x23 => RespType::Boolean(Boolean::from_bytes_unchecked(input)),
+            0x2c => RespType::Double(Double::from_bytes_unchecked(input)),
+            0x28 => RespType::BigNumber(BigNumber::from_bytes_unchecked(input)),
+            0x25 => RespType::Map(Map::from_bytes_unchecked(input)),
+            0x7e => RespType::Set(Set::from_bytes_unchecked(input)),
+            0x3e => RespType::Push(Push::from_bytes_unchecked(input)),
+            0x7c => RespType::Attribute(Attribute::from_bytes_unchecked(input)),
+            0x21 => RespType::BlobError(BlobError::from_bytes_unchecked(input)),
+            0x3d => RespType::VerbatimString(VerbatimString::from_bytes_unchecked(input)),
+            _ => unreachable!("validated value has an unknown type byte"),
+        }
+    }
+
+    /// Build any RESP value from `input`, checking that it holds exactly one valid value
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{RespErrorKind, RespType, SimpleString};
+    /// use bytes::Bytes;
+    ///
+    /// let value = RespType::try_from_bytes(Bytes::from_static(b"+OK\r\n")).unwrap();
+    /// assert_eq!(value, RespType::SimpleString(SimpleString::new(b"OK")));
+    /// assert_eq!(
+    ///     RespType::try_from_bytes(Bytes::from_static(b"+OK\r\n+OK\r\n")).unwrap_err(),
+    ///     RespErrorKind::TrailingBytes
+    /// );
+    /// ```
+    pub fn try_from_bytes(input: Bytes) -> Result<Self, RespError> {
+        let mut index = 0;
+        let is_streamed =
+            Self::while_valid_streamed(&input, &mut index, &input.len(), &Limits::unlimited())?;
+        if index != input.len() {
+            return Err(RespError::new(
+                RespErrorKind::TrailingBytes,
+                &input,
+                &input.len(),
+                index,
+            ));
+        }
+        Ok(Self::from_bytes_unchecked(streamed::reassemble_bytes(
+            input,
+            is_streamed,
+        )))
+    }
+
+    #[inline]
+    pub fn try_from_slice(input: &[u8]) -> Result<Self, RespError> {
+        Self::try_from_bytes(Bytes::copy_from_slice(input))
+    }
+
+    /// Validate any RESP value, dispatching on its type byte
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::RespType;
+    ///
+    /// let string = "*2\r\n:1\r\n+OK\r\n:2\r\n";
+    /// let mut cursor = 0;
+    /// RespType::while_valid(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+    /// println!("{:?}", cursor); // 15
+    /// ```
+    pub fn while_valid(input: &[u8], start: &mut usize, end: &usize) -> Result<(), RespError> {
+        Self::while_valid_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Validate any RESP value, rejecting input beyond `limits`
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{Limits, RespErrorKind, RespType};
+    ///
+    /// let limits = Limits::new().with_max_depth(2);
+    /// let string = "*1\r\n*1\r\n*1\r\n:1\r\n";
+    /// let error = RespType::while_valid_with(string.as_bytes(), &mut 0, &string.len(), &limits)
+    ///     .unwrap_err();
+    /// assert_eq!(error, RespErrorKind::MaxDepthExceeded);
+    /// assert_eq!(error.path(), &[0, 0]);
+    /// ```
+    pub fn while_valid_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<(), RespError> {
+        Self::while_valid_streamed(input, start, end, limits)?;
+        Ok(())
+    }
+
+    /// Validate any RESP value like [`RespType::while_valid_with`], returning whether it holds
+    /// streamed values
+    pub(crate) fn while_valid_streamed(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<bool, RespError> {
+        limits.while_valid_frame(input, start, end, |input, start, end| {
+            Self::while_valid_nested(input, start, end, limits, 0)
+        })
+    }
+
+    /// Validate any RESP value within aggregates nested `depth` deep, and return whether it
+    /// holds streamed values
+    pub(crate) fn while_valid_nested(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+        depth: usize,
+    ) -> Result<bool, RespError> {
+        if *start >= *end {
+            return Err(RespError::incomplete(None, input, end));
         }
+        match input[*start] {
+            0x2b => SimpleString::while_valid_nested(input, start, end, limits)?,
+            0x2d => Error::while_valid_nested(input, start, end, limits)?,
+            0x3a => Integer::while_valid_nested(input, start, end, limits)?,
+            0x24 => return BulkString::while_valid_nested(input, start, end, limits),
+            0x5f => Null::while_valid_nested(input, start, end, limits)?,
+            0x23 => Boolean::while_valid_nested(input, start, end, limits)?,
+            0x2c => Double::while_valid_nested(input, start, end, limits)?,
+            0x28 => BigNumber::while_valid_nested(input, start, end, limits)?,
+            0x21 => BlobError::while_valid_nested(input, start, end, limits)?,
+            0x3d => VerbatimString::while_valid_nested(input, start, end, limits)?,
+            first_char if aggregate::is_aggregate(first_char) => {
+                return aggregate::while_valid(first_char, input, start, end, limits, depth + 1)
+            }
+            _ => {
+                return Err(RespError::new(
+                    RespErrorKind::InvalidFirstChar,
+                    input,
+                    end,
+                    *start,
+                ))
+            }
+        }
+        Ok(false)
+    }
+
+    /// Parse any RESP value, dispatching on its type byte
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{RespType, SimpleString};
+    ///
+    /// let string = "+OK\r\n";
+    /// let value: RespType = RespType::parse(string.as_bytes(), &mut 0, &string.len()).unwrap();
+    /// assert_eq!(value, RespType::SimpleString(SimpleString::new(b"OK")));
+    /// ```
+    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, RespError> {
+        Self::parse_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Parse any RESP value, rejecting input beyond `limits`
+    pub fn parse_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        if *start >= *end {
+            return Err(RespError::incomplete(None, input, end));
+        }
+        match input[*start] {
+            0x2b => SimpleString::parse_with(input, start, end, limits).map(RespType::SimpleString),
+            0x2d => Error::parse_with(input, start, end, limits).map(RespType::Error),
+            0x3a => Integer::parse_with(input, start, end, limits).map(RespType::Integer),
+            0x24 => BulkString::parse_with(input, start, end, limits).map(RespType::BulkString),
+            0x2a => Array::parse_with(input, start, end, limits).map(RespType::Array),
+            0x5f => Null::parse_with(input, start, end, limits).map(RespType::Null),
+            0x23 => Boolean::parse_with(input, start, end, limits).map(RespType::Boolean),
+            0x2c => Double::parse_with(input, start, end, limits).map(RespType::Double),
+            0x28 => BigNumber::parse_with(input, start, end, limits).map(RespType::BigNumber),
+            0x25 => Map::parse_with(input, start, end, limits).map(RespType::Map),
+            0x7e => Set::parse_with(input, start, end, limits).map(RespType::Set),
+            0x3e => Push::parse_with(input, start, end, limits).map(RespType::Push),
+            0x7c => Attribute::parse_with(input, start, end, limits).map(RespType::Attribute),
+            0x21 => BlobError::parse_with(input, start, end, limits).map(RespType::BlobError),
+            0x3d => {
+                VerbatimString::parse_with(input, start, end, limits).map(RespType::VerbatimString)
+            }
+            _ => Err(RespError::new(
+                RespErrorKind::InvalidFirstChar,
+                input,
+                end,
+                *start,
+            )),
+        }
+    }
+
+    /// Parse any RESP value from `input`, sharing its buffer instead of copying it
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::RespType;
+    /// use bytes::Bytes;
+    ///
+    /// let input = Bytes::from_static(b"+OK\r\n:1\r\n");
+    /// let mut cursor = 0;
+    /// let value = RespType::parse_bytes(&input, &mut cursor).unwrap();
+    /// assert_eq!(value.bytes().as_ptr(), input.as_ptr());
+    /// let value = RespType::parse_bytes(&input, &mut cursor).unwrap();
+    /// assert_eq!(value.bytes().as_ptr(), input[5..].as_ptr());
+    /// ```
+    pub fn parse_bytes(input: &Bytes, start: &mut usize) -> Result<Self, RespError> {
+        Self::parse_bytes_with(input, start, &Limits::unlimited())
+    }
+
+    /// Parse any RESP value from `input` like [`RespType::parse_bytes`], rejecting input beyond
+    /// `limits`
+    pub fn parse_bytes_with(
+        input: &Bytes,
+        start: &mut usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        let is_streamed = Self::while_valid_streamed(input, &mut index, &input.len(), limits)?;
+        let value = Self::from_bytes_unchecked(streamed::reassemble_bytes(
+            input.slice(*start..index),
+            is_streamed,
+        ));
+        *start = index;
+        Ok(value)
+    }
+}
+
+impl TryFrom<Bytes> for RespType {
+    type Error = RespError;
+
+    fn try_from(input: Bytes) -> Result<Self, RespError> {
+        Self::try_from_bytes(input)
+    }
+}
+
+macro_rules! impl_try_from_owned {
+    ($($type:ty),*) => {
+        $(
+            impl TryFrom<Vec<u8>> for $type {
+                type Error = RespError;
+
+                fn try_from(input: Vec<u8>) -> Result<Self, RespError> {
+                    Self::try_from_bytes(Bytes::from(input))
+                }
+            }
+
+            impl TryFrom<String> for $type {
+                type Error = RespError;
+
+                fn try_from(input: String) -> Result<Self, RespError> {
+                    Self::try_from_bytes(Bytes::from(input))
+                }
+            }
+
+            impl TryFrom<Box<[u8]>> for $type {
+                type Error = RespError;
+
+                fn try_from(input: Box<[u8]>) -> Result<Self, RespError> {
+                    Self::try_from_bytes(Bytes::from(input))
+                }
+            }
+
+            impl TryFrom<BytesMut> for $type {
+                type Error = RespError;
+
+                fn try_from(input: BytesMut) -> Result<Self, RespError> {
+                    Self::try_from_bytes(input.freeze())
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_owned!(
+    RespType,
+    SimpleString,
+    Error,
+    Integer,
+    BulkString,
+    Array,
+    Null,
+    Boolean,
+    Double,
+    BigNumber,
+    Map,
+    Set,
+    Push,
+    Attribute,
+    BlobError,
+    VerbatimString
+);
+
+#[cfg(test)]
+mod tests_resp_type {
+    use crate::{
+        ArrayBuilder, AttributeBuilder, BigNumber, BlobError, Boolean, BulkString, Double, Error,
+        Integer, MapBuilder, Null, PushBuilder, RespErrorKind, RespType, SetBuilder, SimpleString,
+        VerbatimString, NULL_BULK_STRING,
+    };
+    use bytes::Bytes;
+
+    #[test]
+    fn test_parse_simple_string() {
+        let string = "+OK\r\n";
+        let mut cursor = 0;
+        assert_eq!(
+            RespType::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap(),
+            RespType::SimpleString(SimpleString::new(b"OK"))
+        );
+        assert_eq!(cursor, 5);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let string = "-ERR unknown\r\n";
+        let mut cursor = 0;
+        assert_eq!(
+            RespType::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap(),
+            RespType::Error(Error::new(b"ERR unknown"))
+        );
+        assert_eq!(cursor, 14);
+    }
+
+    #[test]
+    fn test_parse_integer() {
+        let string = ":-100\r\n";
+        let mut cursor = 0;
+        assert_eq!(
+            RespType::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap(),
+            RespType::Integer(Integer::new(-100))
+        );
+        assert_eq!(cursor, 7);
+    }
+
+    #[test]
+    fn test_parse_bulk_string() {
+        let string = "$-1\r\n";
+        let mut cursor = 0;
+        assert_eq!(
+            RespType::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap(),
+            RespType::BulkString(NULL_BULK_STRING)
+        );
+        assert_eq!(cursor, 5);
+    }
+
+    #[test]
+    fn test_parse_array() {
+        let string = "*2\r\n:1\r\n$3\r\nfoo\r\n+OK\r\n";
+        let mut cursor = 0;
+        let array = ArrayBuilder::new()
+            .insert(RespType::Integer(Integer::new(1)))
+            .insert(RespType::BulkString(BulkString::new(b"foo")))
+            .build();
+        assert_eq!(
+            RespType::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap(),
+            RespType::Array(array)
+        );
+        assert_eq!(cursor, 17);
+    }
+
+    #[test]
+    fn test_parse_resp3_scalars() {
+        let string = "_\r\n#t\r\n,1.5\r\n(12345678901234567890\r\n";
+        let mut cursor = 0;
+        let end = string.len();
+        let mut values = Vec::new();
+        while cursor < end {
+            values.push(RespType::parse(string.as_bytes(), &mut cursor, &end).unwrap());
+        }
+        assert_eq!(
+            values,
+            vec![
+                RespType::Null(Null::new()),
+                RespType::Boolean(Boolean::new(true)),
+                RespType::Double(Double::new(1.5)),
+                RespType::BigNumber(BigNumber::new(b"12345678901234567890")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_resp3_aggregates() {
+        let string = "%1\r\n+key\r\n~1\r\n:1\r\n>1\r\n+pong\r\n|1\r\n+ttl\r\n:10\r\n+OK\r\n";
+        let mut cursor = 0;
+        let end = string.len();
+        let map = MapBuilder::new()
+            .insert(
+                RespType::SimpleString(SimpleString::new(b"key")),
+                RespType::Set(
+                    SetBuilder::new()
+                        .insert(RespType::Integer(Integer::new(1)))
+                        .build(),
+                ),
+            )
+            .build();
+        assert_eq!(
+            RespType::parse(string.as_bytes(), &mut cursor, &end).unwrap(),
+            RespType::Map(map)
+        );
+        let push = PushBuilder::new()
+            .insert(RespType::SimpleString(SimpleString::new(b"pong")))
+            .build();
+        assert_eq!(
+            RespType::parse(string.as_bytes(), &mut cursor, &end).unwrap(),
+            RespType::Push(push)
+        );
+        let attribute = AttributeBuilder::new()
+            .insert(
+                RespType::SimpleString(SimpleString::new(b"ttl")),
+                RespType::Integer(Integer::new(10)),
+            )
+            .build(RespType::SimpleString(SimpleString::new(b"OK")));
+        assert_eq!(
+            RespType::parse(string.as_bytes(), &mut cursor, &end).unwrap(),
+            RespType::Attribute(attribute)
+        );
+        assert_eq!(cursor, end);
+    }
+
+    #[test]
+    fn test_parse_blob_error_and_verbatim_string_in_array() {
+        let string = "*2\r\n!3\r\nERR\r\n=7\r\ntxt:foo\r\n";
+        let array = ArrayBuilder::new()
+            .insert(RespType::BlobError(BlobError::new(b"ERR")))
+            .insert(RespType::VerbatimString(VerbatimString::new(
+                b"txt", b"foo",
+            )))
+            .build();
+        assert_eq!(
+            RespType::parse(string.as_bytes(), &mut 0, &string.len()).unwrap(),
+            RespType::Array(array)
+        );
+    }
+
+    #[test]
+    fn test_while_valid_sequence() {
+        let string = "+OK\r\n:1\r\n$3\r\nfoo\r\n";
+        let mut cursor = 0;
+        let end = string.len();
+        let mut count = 0;
+        while cursor < end {
+            RespType::while_valid(string.as_bytes(), &mut cursor, &end).unwrap();
+            count += 1;
+        }
+        assert_eq!(count, 3);
+        assert_eq!(cursor, end);
+    }
+
+    #[test]
+    #[should_panic(expected = "InvalidFirstChar")]
+    fn test_parse_unknown_type() {
+        let string = "?foo\r\n";
+        RespType::parse(string.as_bytes(), &mut 0, &string.len()).unwrap();
+    }
+
+    #[test]
+    fn test_parse_empty_input() {
+        assert_eq!(
+            RespType::parse(b"", &mut 0, &0).unwrap_err(),
+            RespErrorKind::Incomplete { needed: None }
+        );
+    }
+
+    #[test]
+    fn test_try_from_bytes() {
+        assert_eq!(
+            RespType::try_from_slice(b"#t\r\n"),
+            Ok(RespType::Boolean(Boolean::new(true)))
+        );
+        assert_eq!(
+            RespType::try_from_slice(b"*?\r\n:1\r\n.\r\n"),
+            Ok(RespType::Array(
+                ArrayBuilder::new()
+                    .insert(RespType::Integer(Integer::new(1)))
+                    .build()
+            ))
+        );
+        assert_eq!(
+            RespType::try_from_slice(b"").unwrap_err(),
+            RespErrorKind::Incomplete { needed: None }
+        );
+        assert_eq!(
+            RespType::try_from_slice(b"x\r\n").unwrap_err(),
+            RespErrorKind::InvalidFirstChar
+        );
+        assert_eq!(
+            RespType::try_from_slice(b"_\r\n_").unwrap_err(),
+            RespErrorKind::TrailingBytes
+        );
+    }
+
+    #[test]
+    fn test_try_from_owned() {
+        use bytes::BytesMut;
+        use std::convert::TryFrom;
+
+        let expected = RespType::BulkString(BulkString::new(b"foo"));
+        let vector = Vec::from("$3\r\nfoo\r\n");
+        let ptr = vector.as_ptr();
+        let value = RespType::try_from(vector).unwrap();
+        assert_eq!(value, expected);
+        assert_eq!(value.bytes().as_ptr(), ptr);
+        assert_eq!(
+            RespType::try_from(String::from("$3\r\nfoo\r\n")),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            RespType::try_from(Box::<[u8]>::from(&b"$3\r\nfoo\r\n"[..])),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            RespType::try_from(BytesMut::from(&b"$3\r\nfoo\r\n"[..])),
+            Ok(expected)
+        );
+        assert_eq!(
+            SimpleString::try_from(String::from("+OK\r\n\r\n")).unwrap_err(),
+            RespErrorKind::TrailingBytes
+        );
+        assert_eq!(
+            Integer::try_from(Vec::from(":1x\r\n")).unwrap_err(),
+            RespErrorKind::InvalidInteger
+        );
+    }
+
+    #[test]
+    fn test_parse_bytes() {
+        let input = Bytes::from(Vec::from(
+            "*2\r\n:1\r\n$3\r\nfoo\r\n$?\r\n;3\r\nbar\r\n;0\r\n",
+        ));
+        let mut cursor = 0;
+        let value = RespType::parse_bytes(&input, &mut cursor).unwrap();
+        assert_eq!(value.bytes().as_ptr(), input.as_ptr());
+        assert_eq!(cursor, 17);
+        let value = RespType::parse_bytes(&input, &mut cursor).unwrap();
+        assert_eq!(value, RespType::BulkString(BulkString::new(b"bar")));
+        assert_eq!(cursor, input.len());
+        assert_eq!(
+            RespType::parse_bytes(&input, &mut cursor).unwrap_err(),
+            RespErrorKind::Incomplete { needed: None }
+        );
+    }
+
+    #[test]
+    fn test_parse_bytes_question_mark() {
+        let input = Bytes::from(Vec::from("*2\r\n+?\r\n$5\r\nwhat?\r\n"));
+        let value = RespType::parse_bytes(&input, &mut 0).unwrap();
+        assert_eq!(value.bytes().as_ptr(), input.as_ptr());
+        let value = RespType::try_from_bytes(input.clone()).unwrap();
+        assert_eq!(value.bytes().as_ptr(), input.as_ptr());
     }
 }
@@ -0,0 +1,389 @@
+use crate::{RespError, RespErrorKind};
+
+/// Limits enforced while validating input
+///
+/// Bound the resources a peer can make a parser spend, whatever it declares. The default
+/// limits follow the ones of the Redis server, except for the frame size, which is unlimited.
+///
+/// Limits apply through the `_with` parsers, and by default through `Decoder` and `RespCodec`.
+/// For backwards compatibility, the plain `parse` and `while_valid` stay unlimited, so prefer the
+/// `_with` parsers for untrusted input.
+///
+/// # Example
+/// ``` rust
+/// use resp_protocol::{Array, Limits, RespErrorKind};
+///
+/// let limits = Limits::new().with_max_element_count(2);
+/// let string = "*3\r\n:1\r\n:2\r\n:3\r\n";
+/// let error = Array::parse_with(string.as_bytes(), &mut 0, &string.len(), &limits).unwrap_err();
+/// assert_eq!(error.kind(), RespErrorKind::MaxElementCountExceeded);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    max_depth: usize,
+    max_bulk_length: usize,
+    max_element_count: usize,
+    max_frame_size: usize,
+    max_inline_length: usize,
+}
+
+impl Limits {
+    /// Build the default limits
+    ///
+    /// | Limit               | Default    |
+    /// |---------------------|------------|
+    /// | `max_depth`         | 128        |
+    /// | `max_bulk_length`   | 512 MiB    |
+    /// | `max_element_count` | 2147483647 |
+    /// | `max_frame_size`    | unlimited  |
+    /// | `max_inline_length` | 64 KiB     |
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            max_depth: 128,
+            max_bulk_length: 512 * 1024 * 1024,
+            max_element_count: i32::MAX as usize,
+            max_frame_size: usize::MAX,
+            max_inline_length: 64 * 1024,
+        }
+    }
+
+    /// Build limits which never reject anything
+    #[inline]
+    pub const fn unlimited() -> Self {
+        Self {
+            max_depth: usize::MAX,
+            max_bulk_length: usize::MAX,
+            max_element_count: usize::MAX,
+            max_frame_size: usize::MAX,
+            max_inline_length: usize::MAX,
+        }
+    }
+
+    /// Maximum nesting of aggregates, a top-level aggregate being at depth 1
+    #[inline]
+    pub const fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Maximum declared length of a Bulk String, Blob Error or Verbatim String,
+    /// and of each chunk of a streamed string
+    #[inline]
+    pub const fn with_max_bulk_length(mut self, max_bulk_length: usize) -> Self {
+        self.max_bulk_length = max_bulk_length;
+        self
+    }
+
+    /// Maximum number of elements of an aggregate, counting entries for Maps and Attributes
+    #[inline]
+    pub const fn with_max_element_count(mut self, max_element_count: usize) -> Self {
+        self.max_element_count = max_element_count;
+        self
+    }
+
+    /// Maximum encoded length of a top-level value
+    #[inline]
+    pub const fn with_max_frame_size(mut self, max_frame_size: usize) -> Self {
+        self.max_frame_size = max_frame_size;
+        self
+    }
+
+    /// Maximum length of a line, the value of a Simple String, Error, Integer, Double
+    /// or Big Number
+    #[inline]
+    pub const fn with_max_inline_length(mut self, max_inline_length: usize) -> Self {
+        self.max_inline_length = max_inline_length;
+        self
+    }
+
+    #[inline]
+    pub const fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    #[inline]
+    pub const fn max_bulk_length(&self) -> usize {
+        self.max_bulk_length
+    }
+
+    #[inline]
+    pub const fn max_element_count(&self) -> usize {
+        self.max_element_count
+    }
+
+    #[inline]
+    pub const fn max_frame_size(&self) -> usize {
+        self.max_frame_size
+    }
+
+    #[inline]
+    pub const fn max_inline_length(&self) -> usize {
+        self.max_inline_length
+    }
+
+    /// Check the nesting `depth` of the aggregate starting at `offset`
+    pub(crate) fn check_depth(
+        &self,
+        depth: usize,
+        input: &[u8],
+        end: &usize,
+        offset: usize,
+    ) -> Result<(), RespError> {
+        if depth > self.max_depth {
+            return Err(RespError::new(
+                RespErrorKind::MaxDepthExceeded,
+                input,
+                end,
+                offset,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check the declared `length` of a blob, read at `offset`
+    pub(crate) fn check_bulk_length(
+        &self,
+        length: usize,
+        input: &[u8],
+        end: &usize,
+        offset: usize,
+    ) -> Result<(), RespError> {
+        if length > self.max_bulk_length {
+            return Err(RespError::new(
+                RespErrorKind::MaxBulkLengthExceeded,
+                input,
+                end,
+                offset,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check the number of elements of an aggregate, declared or counted at `offset`
+    pub(crate) fn check_element_count(
+        &self,
+        count: usize,
+        input: &[u8],
+        end: &usize,
+        offset: usize,
+    ) -> Result<(), RespError> {
+        if count > self.max_element_count {
+            return Err(RespError::new(
+                RespErrorKind::MaxElementCountExceeded,
+                input,
+                end,
+                offset,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validate the frame starting at `start` with `while_valid`, rejecting it once it is
+    /// known to be longer than `max_frame_size`
+    ///
+    /// `while_valid` is only given the input up to the frame size limit, so a frame
+    /// stops being validated as soon as it crosses the limit.
+    pub(crate) fn while_valid_frame<F, T>(
+        &self,
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        while_valid: F,
+    ) -> Result<T, RespError>
+    where
+        F: FnOnce(&[u8], &mut usize, &usize) -> Result<T, RespError>,
+    {
+        let frame_start = *start;
+        let frame_end = (*end).min(frame_start.saturating_add(self.max_frame_size));
+        while_valid(input, start, &frame_end).map_err(|error| match error.kind() {
+            RespErrorKind::Incomplete { needed }
+                if (frame_end - frame_start).saturating_add(needed.unwrap_or(1))
+                    > self.max_frame_size =>
+            {
+                RespError::new(
+                    RespErrorKind::MaxFrameSizeExceeded,
+                    input,
+                    end,
+                    frame_start.saturating_add(self.max_frame_size),
+                )
+            }
+            _ => error,
+        })
+    }
+
+    /// Check the line starting at `start`, scanned up to `index`
+    pub(crate) fn check_inline_length(
+        &self,
+        start: usize,
+        index: usize,
+        input: &[u8],
+        end: &usize,
+    ) -> Result<(), RespError> {
+        if index - start > self.max_inline_length {
+            return Err(RespError::new(
+                RespErrorKind::MaxInlineLengthExceeded,
+                input,
+                end,
+                start + self.max_inline_length,
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests_limits {
+    use crate::{Array, BulkString, Decoder, Limits, Map, RespErrorKind, RespType, SimpleString};
+
+    #[test]
+    fn test_max_depth() {
+        let limits = Limits::new();
+        let string = format!("{}:1\r\n", "*1\r\n".repeat(128));
+        assert_eq!(
+            RespType::while_valid_with(string.as_bytes(), &mut 0, &string.len(), &limits),
+            Ok(())
+        );
+        let string = format!("{}:1\r\n", "*1\r\n".repeat(129));
+        let error = RespType::while_valid_with(string.as_bytes(), &mut 0, &string.len(), &limits)
+            .unwrap_err();
+        assert_eq!(error, RespErrorKind::MaxDepthExceeded);
+        assert_eq!(error.offset(), 128 * 4);
+        assert_eq!(error.path().len(), 128);
+        let limits = Limits::new().with_max_depth(1);
+        let string = "*1\r\n%1\r\n:1\r\n:2\r\n";
+        let error =
+            Array::parse_with(string.as_bytes(), &mut 0, &string.len(), &limits).unwrap_err();
+        assert_eq!(error, RespErrorKind::MaxDepthExceeded);
+        assert_eq!(error.type_name(), Some("Map"));
+    }
+
+    #[test]
+    fn test_max_bulk_length() {
+        let string = "$9999999999\r\n";
+        let error =
+            BulkString::while_valid_with(string.as_bytes(), &mut 0, &string.len(), &Limits::new())
+                .unwrap_err();
+        assert_eq!(error, RespErrorKind::MaxBulkLengthExceeded);
+        assert_eq!(error.offset(), 1);
+        let limits = Limits::new().with_max_bulk_length(3);
+        let string = "*2\r\n$3\r\nfoo\r\n$?\r\n;2\r\nba\r\n;4\r\nrbaz\r\n;0\r\n";
+        let error =
+            RespType::parse_with(string.as_bytes(), &mut 0, &string.len(), &limits).unwrap_err();
+        assert_eq!(error, RespErrorKind::MaxBulkLengthExceeded);
+        assert_eq!(error.offset(), 26);
+        assert_eq!(error.path(), &[1]);
+    }
+
+    #[test]
+    fn test_max_element_count() {
+        let limits = Limits::new().with_max_element_count(2);
+        let string = "*2\r\n:1\r\n:2\r\n";
+        assert!(Array::parse_with(string.as_bytes(), &mut 0, &string.len(), &limits).is_ok());
+        let string = "*9999999999\r\n";
+        assert_eq!(
+            Array::while_valid_with(string.as_bytes(), &mut 0, &string.len(), &limits).unwrap_err(),
+            RespErrorKind::MaxElementCountExceeded
+        );
+        let string = "%2\r\n+a\r\n:1\r\n+b\r\n:2\r\n";
+        assert!(Map::parse_with(string.as_bytes(), &mut 0, &string.len(), &limits).is_ok());
+        let string = "%?\r\n+a\r\n:1\r\n+b\r\n:2\r\n+c\r\n:3\r\n.\r\n";
+        let error =
+            Map::while_valid_with(string.as_bytes(), &mut 0, &string.len(), &limits).unwrap_err();
+        assert_eq!(error, RespErrorKind::MaxElementCountExceeded);
+        assert_eq!(error.offset(), 20);
+    }
+
+    #[test]
+    fn test_max_frame_size() {
+        let limits = Limits::new().with_max_frame_size(5);
+        let string = "+OK\r\n";
+        assert!(
+            SimpleString::parse_with(string.as_bytes(), &mut 0, &string.len(), &limits).is_ok()
+        );
+        let string = "+OK\r\n+QUEUED\r\n";
+        let mut cursor = 5;
+        let error = RespType::parse_with(string.as_bytes(), &mut cursor, &string.len(), &limits)
+            .unwrap_err();
+        assert_eq!(error, RespErrorKind::MaxFrameSizeExceeded);
+        assert_eq!(error.offset(), 10);
+        assert_eq!(cursor, 5);
+        let string = "$100\r\n";
+        assert_eq!(
+            BulkString::while_valid_with(string.as_bytes(), &mut 0, &string.len(), &limits)
+                .unwrap_err(),
+            RespErrorKind::MaxFrameSizeExceeded
+        );
+    }
+
+    #[test]
+    fn test_max_inline_length() {
+        let limits = Limits::new().with_max_inline_length(4);
+        let string = "+QUEUED\r\n";
+        let error =
+            SimpleString::while_valid_with(string.as_bytes(), &mut 0, &string.len(), &limits)
+                .unwrap_err();
+        assert_eq!(error, RespErrorKind::MaxInlineLengthExceeded);
+        assert_eq!(error.offset(), 5);
+        assert_eq!(error.type_name(), Some("SimpleString"));
+        let string = ":123456";
+        assert_eq!(
+            RespType::while_valid_with(string.as_bytes(), &mut 0, &string.len(), &limits)
+                .unwrap_err(),
+            RespErrorKind::MaxInlineLengthExceeded
+        );
+    }
+
+    #[test]
+    fn test_unlimited() {
+        let string = format!("{}:1\r\n", "*1\r\n".repeat(200));
+        let array = Array::parse_with(
+            string.as_bytes(),
+            &mut 0,
+            &string.len(),
+            &Limits::unlimited(),
+        )
+        .unwrap();
+        assert_eq!(array.count(), 1);
+    }
+
+    #[test]
+    fn test_plain_parsing_is_unlimited() {
+        let string = format!("+{}\r\n", "a".repeat(70_000));
+        let simple_string = SimpleString::parse(string.as_bytes(), &mut 0, &string.len()).unwrap();
+        assert_eq!(simple_string.len(), string.len());
+        assert_eq!(
+            SimpleString::parse_with(string.as_bytes(), &mut 0, &string.len(), &Limits::new())
+                .unwrap_err(),
+            RespErrorKind::MaxInlineLengthExceeded
+        );
+    }
+
+    #[test]
+    fn test_decoder() {
+        let mut decoder = Decoder::with_limits(Limits::new().with_max_depth(2));
+        decoder.extend_from_slice(b"*1\r\n*1\r\n");
+        assert_eq!(decoder.decode().unwrap(), None);
+        decoder.extend_from_slice(b"*1\r\n");
+        let error = decoder.decode().unwrap_err();
+        assert_eq!(error, RespErrorKind::MaxDepthExceeded);
+        assert_eq!(error.path(), &[0, 0]);
+        let mut decoder = Decoder::with_limits(Limits::new().with_max_element_count(1));
+        decoder.extend_from_slice(b"~?\r\n:1\r\n:2");
+        assert_eq!(
+            decoder.decode().unwrap_err(),
+            RespErrorKind::MaxElementCountExceeded
+        );
+        let mut decoder = Decoder::with_limits(Limits::new().with_max_frame_size(16));
+        decoder.extend_from_slice(b"*2\r\n$100\r\n");
+        let error = decoder.decode().unwrap_err();
+        assert_eq!(error, RespErrorKind::MaxFrameSizeExceeded);
+        assert_eq!(error.path(), &[0]);
+    }
+}
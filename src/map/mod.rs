@@ -0,0 +1,309 @@
+use crate::{aggregate, streamed, Limits, RespError, RespErrorKind, RespType};
+use bytes::{BufMut, Bytes, BytesMut};
+use std::convert::TryFrom;
+
+pub const EMPTY_MAP: Map = Map(Bytes::from_static(b"%0\r\n"));
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Map(pub(crate) Bytes);
+
+/// Map type (RESP3)
+impl Map {
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    pub fn bytes(&self) -> Bytes {
+        self.0.clone()
+    }
+
+    #[inline]
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self == EMPTY_MAP
+    }
+
+    #[inline]
+    pub fn from_bytes_unchecked(input: Bytes) -> Self {
+        Self(input)
+    }
+
+    #[inline]
+    pub fn from_slice_unchecked(input: &[u8]) -> Self {
+        let bytes = Bytes::copy_from_slice(input);
+        Self::from_bytes_unchecked(bytes)
+    }
+
+    /// Build a new Map from `input`, checking that it holds exactly one valid value
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{Map, RespErrorKind};
+    /// use bytes::Bytes;
+    ///
+    /// assert!(Map::try_from_bytes(Bytes::from_static(b"%1\r\n+a\r\n:1\r\n")).is_ok());
+    /// assert_eq!(
+    ///     Map::try_from_bytes(Bytes::from_static(b"%1\r\n+a\r\n:1\r\n\r\n")).unwrap_err(),
+    ///     RespErrorKind::TrailingBytes
+    /// );
+    /// ```
+    pub fn try_from_bytes(input: Bytes) -> Result<Self, RespError> {
+        let mut index = 0;
+        let is_streamed =
+            Self::while_valid_streamed(&input, &mut index, &input.len(), &Limits::unlimited())?;
+        if index != input.len() {
+            return Err(RespError::new(
+                RespErrorKind::TrailingBytes,
+                &input,
+                &input.len(),
+                index,
+            ));
+        }
+        Ok(Self::from_bytes_unchecked(streamed::reassemble_bytes(
+            input,
+            is_streamed,
+        )))
+    }
+
+    #[inline]
+    pub fn try_from_slice(input: &[u8]) -> Result<Self, RespError> {
+        Self::try_from_bytes(Bytes::copy_from_slice(input))
+    }
+
+    pub fn while_valid(input: &[u8], start: &mut usize, end: &usize) -> Result<(), RespError> {
+        Self::while_valid_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Validate a Map, whose nesting, entry counts and nested values are bound by `limits`
+    pub fn while_valid_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<(), RespError> {
+        Self::while_valid_streamed(input, start, end, limits)?;
+        Ok(())
+    }
+
+    /// Validate a Map like [`Map::while_valid_with`], returning whether it holds
+    /// streamed values
+    pub(crate) fn while_valid_streamed(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<bool, RespError> {
+        limits.while_valid_frame(input, start, end, |input, start, end| {
+            aggregate::while_valid(0x25, input, start, end, limits, 1)
+        })
+    }
+
+    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, RespError> {
+        Self::parse_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Parse a Map, whose nesting, entry counts and nested values are bound by `limits`
+    pub fn parse_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        let is_streamed = Self::while_valid_streamed(input, &mut index, end, limits)?;
+        let value = Self::from_bytes_unchecked(streamed::copy(&input[*start..index], is_streamed));
+        *start = index;
+        Ok(value)
+    }
+
+    /// Parse a Map from `input`, sharing its buffer instead of copying it
+    ///
+    /// A streamed Map, or any streamed value within it, is reassembled into a new buffer instead.
+    pub fn parse_bytes(input: &Bytes, start: &mut usize) -> Result<Self, RespError> {
+        Self::parse_bytes_with(input, start, &Limits::unlimited())
+    }
+
+    /// Parse a Map from `input` like [`Map::parse_bytes`], whose nesting, entry counts and nested
+    /// values are bound by `limits`
+    pub fn parse_bytes_with(
+        input: &Bytes,
+        start: &mut usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        let is_streamed = Self::while_valid_streamed(input, &mut index, &input.len(), limits)?;
+        let value = Self::from_bytes_unchecked(streamed::reassemble_bytes(
+            input.slice(*start..index),
+            is_streamed,
+        ));
+        *start = index;
+        Ok(value)
+    }
+}
+
+impl TryFrom<Bytes> for Map {
+    type Error = RespError;
+
+    fn try_from(input: Bytes) -> Result<Self, RespError> {
+        Self::try_from_bytes(input)
+    }
+}
+
+impl PartialEq<Map> for &Map {
+    fn eq(&self, other: &Map) -> bool {
+        self.0 == other.bytes()
+    }
+}
+
+pub struct MapBuilder {
+    inner: Vec<(RespType, RespType)>,
+}
+
+impl Default for MapBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MapBuilder {
+    /// Build a new Map Builder
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{Map, MapBuilder};
+    ///
+    /// let map_builder: MapBuilder = MapBuilder::new();
+    /// let map: Map = map_builder.build();
+    /// ```
+    #[inline]
+    pub fn new() -> MapBuilder {
+        MapBuilder {
+            inner: Vec::<(RespType, RespType)>::new(),
+        }
+    }
+
+    #[inline]
+    pub fn value(&mut self) -> Vec<(RespType, RespType)> {
+        self.inner.clone()
+    }
+
+    /// Add a new entry to Map Builder
+    ///
+    /// # Example
+    /// ```rust
+    /// use resp_protocol::{RespType, Map, MapBuilder, Integer, SimpleString};
+    ///
+    /// let mut map_builder: MapBuilder = MapBuilder::new();
+    ///
+    /// map_builder.insert(
+    ///     RespType::SimpleString(SimpleString::new(b"first")),
+    ///     RespType::Integer(Integer::new(1)),
+    /// );
+    ///
+    /// let map: Map = map_builder.build();
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, key: RespType, value: RespType) -> &mut Self {
+        self.inner.push((key, value));
+        self
+    }
+
+    #[inline]
+    pub fn build(&self) -> Map {
+        let length = self.inner.len();
+        if length == 0 {
+            return EMPTY_MAP;
+        }
+        let length_string = length.to_string();
+        let mut total_bytes = length_string.len() + 3;
+        for (key, value) in &self.inner {
+            total_bytes += key.len() + value.len();
+        }
+        let mut bytes = BytesMut::with_capacity(total_bytes);
+        bytes.put_u8(0x25); // "%"
+        bytes.put_slice(length_string.as_bytes());
+        bytes.put_u8(0x0d); // CR
+        bytes.put_u8(0x0a); // LF
+        for (key, value) in &self.inner {
+            bytes.put(key.bytes());
+            bytes.put(value.bytes());
+        }
+        Map(bytes.freeze())
+    }
+}
+
+#[cfg(test)]
+mod tests_map {
+    use crate::{
+        ArrayBuilder, Integer, Map, MapBuilder, RespErrorKind, RespType, SimpleString, EMPTY_MAP,
+    };
+    use bytes::Bytes;
+
+    #[test]
+    fn test_build_empty_map() {
+        assert_eq!(MapBuilder::new().build(), EMPTY_MAP);
+    }
+
+    #[test]
+    fn test_build_map() {
+        let map = MapBuilder::new()
+            .insert(
+                RespType::SimpleString(SimpleString::new(b"first")),
+                RespType::Integer(Integer::new(1)),
+            )
+            .insert(
+                RespType::SimpleString(SimpleString::new(b"second")),
+                RespType::Integer(Integer::new(2)),
+            )
+            .build();
+        assert_eq!(
+            map.bytes(),
+            Bytes::from_static(b"%2\r\n+first\r\n:1\r\n+second\r\n:2\r\n")
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let string = "%2\r\n+first\r\n:1\r\n+second\r\n*1\r\n:2\r\n";
+        let mut cursor = 0;
+        let map = Map::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        let reference_map = MapBuilder::new()
+            .insert(
+                RespType::SimpleString(SimpleString::new(b"first")),
+                RespType::Integer(Integer::new(1)),
+            )
+            .insert(
+                RespType::SimpleString(SimpleString::new(b"second")),
+                RespType::Array(
+                    ArrayBuilder::new()
+                        .insert(RespType::Integer(Integer::new(2)))
+                        .build(),
+                ),
+            )
+            .build();
+        assert_eq!(map, reference_map);
+        assert_eq!(cursor, string.len());
+    }
+
+    #[test]
+    fn test_while_valid_missing_value() {
+        let string = "%1\r\n+first\r\n";
+        assert_eq!(
+            Map::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: None }
+        );
+    }
+
+    #[test]
+    fn test_parse_streamed() {
+        let string = "%?\r\n+first\r\n:1\r\n.\r\n";
+        let map = Map::parse(string.as_bytes(), &mut 0, &string.len()).unwrap();
+        assert_eq!(map.bytes(), Bytes::from_static(b"%1\r\n+first\r\n:1\r\n"));
+    }
+}
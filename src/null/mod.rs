@@ -0,0 +1,216 @@
+use crate::{length, Limits, RespError, RespErrorKind};
+use bytes::Bytes;
+use std::convert::TryFrom;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Null(pub(crate) Bytes);
+
+/// Null type (RESP3)
+impl Null {
+    /// Build a new Null
+    ///
+    /// # Example
+    /// ```
+    /// use resp_protocol::Null;
+    ///
+    /// let null = Null::new();
+    /// println!("{:?}", null); // Null(b"_\r\n")
+    /// ```
+    #[inline]
+    pub fn new() -> Self {
+        Self::from_bytes_unchecked(Bytes::from_static(b"_\r\n"))
+    }
+
+    #[inline]
+    pub fn bytes(&self) -> Bytes {
+        self.0.clone()
+    }
+
+    #[allow(clippy::len_without_is_empty)]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    pub fn from_bytes_unchecked(input: Bytes) -> Self {
+        Self(input)
+    }
+
+    #[inline]
+    pub fn from_slice_unchecked(input: &[u8]) -> Self {
+        let bytes = Bytes::copy_from_slice(input);
+        Self::from_bytes_unchecked(bytes)
+    }
+
+    /// Build a new Null from `input`, checking that it holds exactly one valid value
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{Null, RespErrorKind};
+    /// use bytes::Bytes;
+    ///
+    /// assert!(Null::try_from_bytes(Bytes::from_static(b"_\r\n")).is_ok());
+    /// assert_eq!(
+    ///     Null::try_from_bytes(Bytes::from_static(b"_\r\n\r\n")).unwrap_err(),
+    ///     RespErrorKind::TrailingBytes
+    /// );
+    /// ```
+    pub fn try_from_bytes(input: Bytes) -> Result<Self, RespError> {
+        let mut index = 0;
+        Self::while_valid(&input, &mut index, &input.len())?;
+        if index != input.len() {
+            return Err(RespError::new(
+                RespErrorKind::TrailingBytes,
+                &input,
+                &input.len(),
+                index,
+            ));
+        }
+        Ok(Self::from_bytes_unchecked(input))
+    }
+
+    #[inline]
+    pub fn try_from_slice(input: &[u8]) -> Result<Self, RespError> {
+        Self::try_from_bytes(Bytes::copy_from_slice(input))
+    }
+
+    pub fn while_valid(input: &[u8], start: &mut usize, end: &usize) -> Result<(), RespError> {
+        Self::while_valid_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Validate a Null, rejecting input longer than [`Limits::max_frame_size`]
+    pub fn while_valid_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<(), RespError> {
+        limits.while_valid_frame(input, start, end, |input, start, end| {
+            Self::while_valid_nested(input, start, end, limits)
+        })
+    }
+
+    pub(crate) fn while_valid_nested(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        _limits: &Limits,
+    ) -> Result<(), RespError> {
+        let index = *start;
+        if index >= *end {
+            return Err(RespError::incomplete(None, input, end).within(0x5f));
+        }
+        if input[index] != 0x5f {
+            return Err(
+                RespError::new(RespErrorKind::InvalidFirstChar, input, end, index)
+                    .expecting("_")
+                    .within(0x5f),
+            );
+        }
+        let mut index = index + 1;
+        length::while_valid_token(
+            b"\r\n",
+            RespErrorKind::InvalidTerminate,
+            input,
+            &mut index,
+            end,
+        )
+        .map_err(|error| error.within(0x5f))?;
+        *start = index;
+        Ok(())
+    }
+
+    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, RespError> {
+        Self::parse_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Parse a Null, rejecting input longer than [`Limits::max_frame_size`]
+    pub fn parse_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        Self::while_valid_with(input, &mut index, end, limits)?;
+        let value = Self::from_slice_unchecked(&input[*start..index]);
+        *start = index;
+        Ok(value)
+    }
+
+    /// Parse a Null from `input`, sharing its buffer instead of copying it
+    pub fn parse_bytes(input: &Bytes, start: &mut usize) -> Result<Self, RespError> {
+        Self::parse_bytes_with(input, start, &Limits::unlimited())
+    }
+
+    /// Parse a Null from `input` like [`Null::parse_bytes`], rejecting input longer than
+    /// [`Limits::max_frame_size`]
+    pub fn parse_bytes_with(
+        input: &Bytes,
+        start: &mut usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        Self::while_valid_with(input, &mut index, &input.len(), limits)?;
+        let value = Self::from_bytes_unchecked(input.slice(*start..index));
+        *start = index;
+        Ok(value)
+    }
+}
+
+impl TryFrom<Bytes> for Null {
+    type Error = RespError;
+
+    fn try_from(input: Bytes) -> Result<Self, RespError> {
+        Self::try_from_bytes(input)
+    }
+}
+
+impl Default for Null {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests_null {
+    use crate::null::Null;
+    use crate::RespErrorKind;
+    use bytes::Bytes;
+
+    #[test]
+    fn test_new() {
+        assert_eq!(Null::new(), Null(Bytes::from_static(b"_\r\n")));
+    }
+
+    #[test]
+    fn test_parse() {
+        let string = "_\r\n+bar\r\n";
+        let mut cursor = 0;
+        let end = string.len();
+        assert_eq!(
+            Null::parse(string.as_bytes(), &mut cursor, &end).unwrap(),
+            Null::new()
+        );
+        assert_eq!(cursor, 3);
+    }
+
+    #[test]
+    fn test_while_valid_incomplete() {
+        let string = "_\r";
+        assert_eq!(
+            Null::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: Some(1) }
+        );
+    }
+
+    #[test]
+    fn test_while_valid_invalid_terminate() {
+        let string = "_foo\r\n";
+        assert_eq!(
+            Null::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::InvalidTerminate
+        );
+    }
+}
@@ -0,0 +1,9 @@
+/// Version of the protocol spoken with a peer
+///
+/// Decides how values without a RESP2 type are written, such as maps, which are sent as flat
+/// Arrays of keys and values in RESP2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Resp2,
+    Resp3,
+}
@@ -0,0 +1,253 @@
+use crate::{aggregate, streamed, Limits, RespError, RespErrorKind, RespType};
+use bytes::{BufMut, Bytes, BytesMut};
+use std::convert::TryFrom;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Push(pub(crate) Bytes);
+
+/// Push type (RESP3)
+impl Push {
+    #[allow(clippy::len_without_is_empty)]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    pub fn bytes(&self) -> Bytes {
+        self.0.clone()
+    }
+
+    #[inline]
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+
+    #[inline]
+    pub fn from_bytes_unchecked(input: Bytes) -> Self {
+        Self(input)
+    }
+
+    #[inline]
+    pub fn from_slice_unchecked(input: &[u8]) -> Self {
+        let bytes = Bytes::copy_from_slice(input);
+        Self::from_bytes_unchecked(bytes)
+    }
+
+    /// Build a new Push from `input`, checking that it holds exactly one valid value
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{Push, RespErrorKind};
+    /// use bytes::Bytes;
+    ///
+    /// assert!(Push::try_from_bytes(Bytes::from_static(b">1\r\n:1\r\n")).is_ok());
+    /// assert_eq!(
+    ///     Push::try_from_bytes(Bytes::from_static(b">1\r\n:1\r\n\r\n")).unwrap_err(),
+    ///     RespErrorKind::TrailingBytes
+    /// );
+    /// ```
+    pub fn try_from_bytes(input: Bytes) -> Result<Self, RespError> {
+        let mut index = 0;
+        let is_streamed =
+            Self::while_valid_streamed(&input, &mut index, &input.len(), &Limits::unlimited())?;
+        if index != input.len() {
+            return Err(RespError::new(
+                RespErrorKind::TrailingBytes,
+                &input,
+                &input.len(),
+                index,
+            ));
+        }
+        Ok(Self::from_bytes_unchecked(streamed::reassemble_bytes(
+            input,
+            is_streamed,
+        )))
+    }
+
+    #[inline]
+    pub fn try_from_slice(input: &[u8]) -> Result<Self, RespError> {
+        Self::try_from_bytes(Bytes::copy_from_slice(input))
+    }
+
+    pub fn while_valid(input: &[u8], start: &mut usize, end: &usize) -> Result<(), RespError> {
+        Self::while_valid_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Validate a Push, whose nesting, element counts and nested values are bound by `limits`
+    pub fn while_valid_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<(), RespError> {
+        Self::while_valid_streamed(input, start, end, limits)?;
+        Ok(())
+    }
+
+    /// Validate a Push like [`Push::while_valid_with`], returning whether it holds
+    /// streamed values
+    pub(crate) fn while_valid_streamed(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<bool, RespError> {
+        limits.while_valid_frame(input, start, end, |input, start, end| {
+            aggregate::while_valid(0x3e, input, start, end, limits, 1)
+        })
+    }
+
+    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, RespError> {
+        Self::parse_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Parse a Push, whose nesting, element counts and nested values are bound by `limits`
+    pub fn parse_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        let is_streamed = Self::while_valid_streamed(input, &mut index, end, limits)?;
+        let value = Self::from_bytes_unchecked(streamed::copy(&input[*start..index], is_streamed));
+        *start = index;
+        Ok(value)
+    }
+
+    /// Parse a Push from `input`, sharing its buffer instead of copying it
+    ///
+    /// Streamed values within it are reassembled into a new buffer instead.
+    pub fn parse_bytes(input: &Bytes, start: &mut usize) -> Result<Self, RespError> {
+        Self::parse_bytes_with(input, start, &Limits::unlimited())
+    }
+
+    /// Parse a Push from `input` like [`Push::parse_bytes`], whose nesting, element counts and
+    /// nested values are bound by `limits`
+    pub fn parse_bytes_with(
+        input: &Bytes,
+        start: &mut usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        let is_streamed = Self::while_valid_streamed(input, &mut index, &input.len(), limits)?;
+        let value = Self::from_bytes_unchecked(streamed::reassemble_bytes(
+            input.slice(*start..index),
+            is_streamed,
+        ));
+        *start = index;
+        Ok(value)
+    }
+}
+
+impl TryFrom<Bytes> for Push {
+    type Error = RespError;
+
+    fn try_from(input: Bytes) -> Result<Self, RespError> {
+        Self::try_from_bytes(input)
+    }
+}
+
+impl PartialEq<Push> for &Push {
+    fn eq(&self, other: &Push) -> bool {
+        self.0 == other.bytes()
+    }
+}
+
+pub struct PushBuilder {
+    inner: Vec<RespType>,
+}
+
+impl Default for PushBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PushBuilder {
+    /// Build a new Push Builder
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{Push, PushBuilder};
+    ///
+    /// let push_builder: PushBuilder = PushBuilder::new();
+    /// let push: Push = push_builder.build();
+    /// ```
+    #[inline]
+    pub fn new() -> PushBuilder {
+        PushBuilder {
+            inner: Vec::<RespType>::new(),
+        }
+    }
+
+    #[inline]
+    pub fn value(&mut self) -> Vec<RespType> {
+        self.inner.clone()
+    }
+
+    /// Add a new value to Push Builder
+    ///
+    /// # Example
+    /// ```rust
+    /// use resp_protocol::{RespType, Push, PushBuilder, SimpleString};
+    ///
+    /// let mut push_builder: PushBuilder = PushBuilder::new();
+    ///
+    /// push_builder.insert(RespType::SimpleString(SimpleString::new(b"OK")));
+    ///
+    /// let push: Push = push_builder.build();
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, value: RespType) -> &mut Self {
+        self.inner.push(value);
+        self
+    }
+
+    #[inline]
+    pub fn build(&self) -> Push {
+        let length = self.inner.len();
+        let length_string = length.to_string();
+        let mut total_bytes = length_string.len() + 3;
+        for element in &self.inner {
+            total_bytes += element.len();
+        }
+        let mut bytes = BytesMut::with_capacity(total_bytes);
+        bytes.put_u8(0x3e); // ">"
+        bytes.put_slice(length_string.as_bytes());
+        bytes.put_u8(0x0d); // CR
+        bytes.put_u8(0x0a); // LF
+        for element in &self.inner {
+            bytes.put(element.bytes());
+        }
+        Push(bytes.freeze())
+    }
+}
+
+#[cfg(test)]
+mod tests_push {
+    use crate::{BulkString, Push, PushBuilder, RespType};
+    use bytes::Bytes;
+
+    #[test]
+    fn test_build_push() {
+        let push = PushBuilder::new()
+            .insert(RespType::BulkString(BulkString::new(b"message")))
+            .insert(RespType::BulkString(BulkString::new(b"channel")))
+            .build();
+        assert_eq!(
+            push.bytes(),
+            Bytes::from_static(b">2\r\n$7\r\nmessage\r\n$7\r\nchannel\r\n")
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let string = ">3\r\n$7\r\nmessage\r\n$7\r\nchannel\r\n$5\r\nhello\r\n";
+        let mut cursor = 0;
+        let push = Push::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        assert_eq!(push.bytes(), Bytes::from(string));
+        assert_eq!(cursor, string.len());
+    }
+}
@@ -0,0 +1,221 @@
+use crate::{
+    aggregate, Array, BulkString, InlineCommand, Limits, RespError, RespErrorKind, RespType,
+};
+use bytes::Bytes;
+
+/// Request sent by a client, either an Array of Bulk Strings or an inline command
+///
+/// As in Redis, a request starting with `*` is an Array, anything else an inline command.
+///
+/// # Example
+/// ``` rust
+/// use resp_protocol::Request;
+///
+/// let string = "*2\r\n$4\r\nECHO\r\n$3\r\nfoo\r\nECHO foo\r\n";
+/// let mut cursor = 0;
+/// let array = Request::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+/// let inline = Request::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+/// assert_eq!(array.args(), inline.args());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Request {
+    Array(Array),
+    Inline(InlineCommand),
+}
+
+impl Request {
+    #[inline]
+    pub fn bytes(&self) -> Bytes {
+        match self {
+            Request::Array(array) => array.bytes(),
+            Request::Inline(inline_command) => inline_command.bytes(),
+        }
+    }
+
+    #[allow(clippy::len_without_is_empty)]
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self {
+            Request::Array(array) => array.len(),
+            Request::Inline(inline_command) => inline_command.len(),
+        }
+    }
+
+    /// Arguments of the command, the name first
+    pub fn args(&self) -> Vec<Bytes> {
+        match self {
+            Request::Array(array) => array
+                .iter()
+                .map(|value| match value {
+                    RespType::BulkString(bulk_string) => bulk_string
+                        .value()
+                        .expect("Request holds Bulk Strings which are not null"),
+                    _ => unreachable!("Request holds Bulk Strings only"),
+                })
+                .collect(),
+            Request::Inline(inline_command) => inline_command.args(),
+        }
+    }
+
+    /// Arguments as an Array of Bulk Strings, whatever the form of the request
+    #[inline]
+    pub fn to_array(&self) -> Array {
+        match self {
+            Request::Array(array) => array.clone(),
+            Request::Inline(inline_command) => inline_command.to_array(),
+        }
+    }
+
+    pub fn while_valid(input: &[u8], start: &mut usize, end: &usize) -> Result<(), RespError> {
+        Self::while_valid_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Validate a request, rejecting input beyond `limits`
+    ///
+    /// An Array must hold Bulk Strings only, neither null nor streamed.
+    pub fn while_valid_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<(), RespError> {
+        if *start >= *end {
+            return Err(RespError::incomplete(None, input, end));
+        }
+        if input[*start] != 0x2a {
+            return InlineCommand::while_valid_with(input, start, end, limits);
+        }
+        limits.while_valid_frame(input, start, end, |input, start, end| {
+            let mut index = *start;
+            let count = aggregate::while_valid_header(0x2a, input, &mut index, end, limits)?
+                .ok_or_else(|| {
+                    RespError::new(RespErrorKind::InvalidLength, input, end, *start + 1)
+                        .within(0x2a)
+                })?;
+            for validated in 0..count {
+                while_valid_arg(input, &mut index, end, limits).map_err(|error| {
+                    let error = if validated + 1 == count {
+                        error
+                    } else {
+                        error.followed_by_values()
+                    };
+                    error.within(0x2a).nested_in_path(Some(validated))
+                })?;
+            }
+            *start = index;
+            Ok(())
+        })
+    }
+
+    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, RespError> {
+        Self::parse_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Parse a request, rejecting input beyond `limits`
+    pub fn parse_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        Self::while_valid_with(input, &mut index, end, limits)?;
+        let bytes = &input[*start..index];
+        let value = if bytes[0] == 0x2a {
+            Request::Array(Array::from_slice_unchecked(bytes))
+        } else {
+            Request::Inline(InlineCommand::from_slice_unchecked(bytes))
+        };
+        *start = index;
+        Ok(value)
+    }
+}
+
+/// Validate an argument of an Array request, a Bulk String which is neither null nor streamed
+fn while_valid_arg(
+    input: &[u8],
+    start: &mut usize,
+    end: &usize,
+    limits: &Limits,
+) -> Result<(), RespError> {
+    if *start >= *end {
+        return Err(RespError::incomplete(None, input, end));
+    }
+    if input[*start] != 0x24 {
+        return Err(
+            RespError::new(RespErrorKind::InvalidFirstChar, input, end, *start).expecting("$"),
+        );
+    }
+    if *start + 1 < *end && matches!(input[*start + 1], 0x2d | 0x3f) {
+        return Err(
+            RespError::new(RespErrorKind::InvalidLength, input, end, *start + 1)
+                .expecting("digit")
+                .within(0x24),
+        );
+    }
+    BulkString::while_valid_nested(input, start, end, limits)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests_request {
+    use crate::{ArrayBuilder, BulkString, Request, RespErrorKind, RespType};
+    use bytes::Bytes;
+
+    #[test]
+    fn test_parse_either_form() {
+        let string = "*2\r\n$3\r\nSET\r\n$1\r\nk\r\nSET k\r\n";
+        let mut cursor = 0;
+        let expected = ArrayBuilder::new()
+            .insert(RespType::BulkString(BulkString::new(b"SET")))
+            .insert(RespType::BulkString(BulkString::new(b"k")))
+            .build();
+        let request = Request::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        assert!(matches!(request, Request::Array(_)));
+        assert_eq!(request.to_array(), expected);
+        let request = Request::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        assert!(matches!(request, Request::Inline(_)));
+        assert_eq!(request.to_array(), expected);
+        assert_eq!(request.args(), vec![Bytes::from("SET"), Bytes::from("k")]);
+        assert_eq!(cursor, string.len());
+    }
+
+    #[test]
+    fn test_while_valid_invalid_arg() {
+        let string = "*2\r\n$3\r\nGET\r\n:1\r\n";
+        let error = Request::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err();
+        assert_eq!(error, RespErrorKind::InvalidFirstChar);
+        assert_eq!(error.path(), &[1]);
+        assert_eq!(error.expected(), Some("$"));
+        for string in ["*1\r\n$-1\r\n", "*1\r\n$?\r\n;0\r\n", "*?\r\n.\r\n"] {
+            assert_eq!(
+                Request::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+                RespErrorKind::InvalidLength,
+                "{:?}",
+                string
+            );
+        }
+    }
+
+    #[test]
+    fn test_while_valid_incomplete() {
+        for string in ["", "*2\r\n$3\r\nGET\r\n", "GET k"] {
+            assert_eq!(
+                Request::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+                RespErrorKind::Incomplete { needed: None },
+                "{:?}",
+                string
+            );
+        }
+        let string = "*2\r\n$3\r\nGE";
+        assert_eq!(
+            Request::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: None }
+        );
+        let string = "*2\r\n$3\r\nGET\r\n$1\r\n";
+        assert_eq!(
+            Request::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: Some(3) }
+        );
+    }
+}
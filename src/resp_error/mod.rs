@@ -0,0 +1,396 @@
+/// Number of bytes shown on each side of the offending byte
+const CONTEXT_RADIUS: usize = 8;
+
+/// Number of bytes of the context window, held inline so that building an error never allocates
+const CONTEXT_LENGTH: usize = 2 * CONTEXT_RADIUS + 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RespErrorKind {
+    /// The input is a valid prefix of a value, but ends before the value does.
+    ///
+    /// `needed` is the exact number of missing bytes when it is already known,
+    /// e.g. once the length header of a bulk string has been read. Within an aggregate,
+    /// it is only known while no other value is outstanding after the incomplete one.
+    Incomplete {
+        needed: Option<usize>,
+    },
+    InvalidFirstChar,
+    /// The value of an Integer is not `[-]<digits>`, or does not fit in an `i64`.
+    InvalidInteger,
+    InvalidLength,
+    InvalidLengthSeparator,
+    InvalidNullValue,
+    InvalidValue,
+    InvalidTerminate,
+    LengthsNotMatch,
+    /// A validating constructor was given bytes past the end of the value.
+    TrailingBytes,
+    /// An inline command opens a quote without closing it, or does not follow
+    /// a closing quote with a space.
+    UnbalancedQuotes,
+    /// Aggregates are nested deeper than [`Limits::max_depth`](crate::Limits::max_depth).
+    MaxDepthExceeded,
+    /// A declared length is above [`Limits::max_bulk_length`](crate::Limits::max_bulk_length).
+    MaxBulkLengthExceeded,
+    /// An aggregate holds more than [`Limits::max_element_count`](crate::Limits::max_element_count) elements.
+    MaxElementCountExceeded,
+    /// A value is longer than [`Limits::max_frame_size`](crate::Limits::max_frame_size).
+    MaxFrameSizeExceeded,
+    /// A line is longer than [`Limits::max_inline_length`](crate::Limits::max_inline_length).
+    MaxInlineLengthExceeded,
+}
+
+impl std::fmt::Display for RespErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RespErrorKind::Incomplete { needed: None } => {
+                write!(f, "Incomplete input.")
+            }
+            RespErrorKind::Incomplete {
+                needed: Some(needed),
+            } => {
+                write!(f, "Incomplete input, {} more bytes needed.", needed)
+            }
+            RespErrorKind::InvalidFirstChar => {
+                write!(f, "Invalid first char.")
+            }
+            RespErrorKind::InvalidInteger => {
+                write!(f, "Invalid integer.")
+            }
+            RespErrorKind::InvalidLength => {
+                write!(f, "Invalid length.")
+            }
+            RespErrorKind::InvalidLengthSeparator => {
+                write!(f, "Invalid length separator.")
+            }
+            RespErrorKind::InvalidValue => {
+                write!(f, "Invalid value.")
+            }
+            RespErrorKind::InvalidNullValue => {
+                write!(f, "Invalid null value.")
+            }
+            RespErrorKind::LengthsNotMatch => {
+                write!(f, "Lengths do not match.")
+            }
+            RespErrorKind::InvalidTerminate => {
+                write!(f, "Invalid terminate.")
+            }
+            RespErrorKind::TrailingBytes => {
+                write!(f, "Trailing bytes after value.")
+            }
+            RespErrorKind::UnbalancedQuotes => {
+                write!(f, "Unbalanced quotes.")
+            }
+            RespErrorKind::MaxDepthExceeded => {
+                write!(f, "Max depth exceeded.")
+            }
+            RespErrorKind::MaxBulkLengthExceeded => {
+                write!(f, "Max bulk length exceeded.")
+            }
+            RespErrorKind::MaxElementCountExceeded => {
+                write!(f, "Max element count exceeded.")
+            }
+            RespErrorKind::MaxFrameSizeExceeded => {
+                write!(f, "Max frame size exceeded.")
+            }
+            RespErrorKind::MaxInlineLengthExceeded => {
+                write!(f, "Max inline length exceeded.")
+            }
+        }
+    }
+}
+
+/// Error raised while validating RESP input
+///
+/// Besides its kind, an error records where it was raised: the offset of the offending byte
+/// in the input, the byte itself, the token expected in its place, the type of the value
+/// being validated and the path of that value within its enclosing aggregates.
+///
+/// Building an error does not allocate, unless it is nested within aggregates; incomplete
+/// input never records a path, so waiting for more input is cheap.
+///
+/// # Example
+/// ``` rust
+/// use resp_protocol::{RespErrorKind, RespType};
+///
+/// let string = "*2\r\n:1\r\n*1\r\n$3\r\nfooo\r\n";
+/// let error = RespType::parse(string.as_bytes(), &mut 0, &string.len()).unwrap_err();
+/// assert_eq!(error.kind(), RespErrorKind::InvalidTerminate);
+/// assert_eq!(error.offset(), 19);
+/// assert_eq!(error.byte(), Some(b'o'));
+/// assert_eq!(error.type_name(), Some("BulkString"));
+/// assert_eq!(error.path(), &[1, 0]);
+/// println!("{}", error);
+/// // Invalid terminate. At offset 19 in BulkString at [1][0], found 0x6f, expected \r\n.
+/// // Context: 0a 24 33 0d 0a 66 6f 6f [6f] 0d 0a
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RespError {
+    kind: RespErrorKind,
+    offset: usize,
+    byte: Option<u8>,
+    expected: Option<&'static str>,
+    type_name: Option<&'static str>,
+    path: Vec<usize>,
+    context_offset: usize,
+    context_length: usize,
+    context: [u8; CONTEXT_LENGTH],
+}
+
+impl RespError {
+    /// Build a new error raised at `offset` of `input`, which is only valid up to `end`
+    pub(crate) fn new(kind: RespErrorKind, input: &[u8], end: &usize, offset: usize) -> Self {
+        let end = (*end).min(input.len());
+        let context_offset = offset.min(end).saturating_sub(CONTEXT_RADIUS);
+        let context_end = offset.saturating_add(CONTEXT_RADIUS + 1).min(end);
+        let context_length = context_end.saturating_sub(context_offset);
+        let mut context = [0; CONTEXT_LENGTH];
+        context[..context_length].copy_from_slice(&input[context_offset..context_end]);
+        Self {
+            kind,
+            offset,
+            byte: if offset < end {
+                Some(input[offset])
+            } else {
+                None
+            },
+            expected: None,
+            type_name: None,
+            path: Vec::new(),
+            context_offset,
+            context_length,
+            context,
+        }
+    }
+
+    /// Build a new error for `input` ending before the value does, `needed` bytes early if known
+    #[inline]
+    pub(crate) fn incomplete(needed: Option<usize>, input: &[u8], end: &usize) -> Self {
+        Self::new(RespErrorKind::Incomplete { needed }, input, end, *end)
+    }
+
+    /// Forget the number of missing bytes of an incomplete value followed by more values,
+    /// as the whole input then needs more than that
+    #[inline]
+    pub(crate) fn followed_by_values(mut self) -> Self {
+        if let RespErrorKind::Incomplete { needed: Some(_) } = self.kind {
+            self.kind = RespErrorKind::Incomplete { needed: None };
+        }
+        self
+    }
+
+    /// Record the token expected in place of the offending byte
+    #[inline]
+    pub(crate) fn expecting(mut self, expected: &'static str) -> Self {
+        self.expected = Some(expected);
+        self
+    }
+
+    /// Record the type of the value being validated, unless an inner value already did
+    #[inline]
+    pub(crate) fn within(mut self, first_char: u8) -> Self {
+        if self.type_name.is_none() {
+            self.type_name = type_name(first_char);
+        }
+        self
+    }
+
+    /// Record that the error was raised within the enclosing aggregates at `path`, outermost first
+    ///
+    /// Incomplete input is not an error within a value, so it keeps no path.
+    #[inline]
+    pub(crate) fn nested_in_path<I>(mut self, path: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        if let RespErrorKind::Incomplete { .. } = self.kind {
+            return self;
+        }
+        self.path.splice(0..0, path);
+        self
+    }
+
+    /// Move an error raised within a slice of `input` starting at `base` onto `input` itself
+    pub(crate) fn rebase(self, input: &[u8], end: &usize, base: usize) -> Self {
+        Self {
+            expected: self.expected,
+            type_name: self.type_name,
+            path: self.path,
+            ..Self::new(self.kind, input, end, base + self.offset)
+        }
+    }
+
+    #[inline]
+    pub fn kind(&self) -> RespErrorKind {
+        self.kind
+    }
+
+    /// Offset of the offending byte in the input, or its length if the input is incomplete
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Offending byte, `None` if the input is incomplete
+    #[inline]
+    pub fn byte(&self) -> Option<u8> {
+        self.byte
+    }
+
+    /// Token expected in place of the offending byte, such as `\r\n` or `digit`
+    #[inline]
+    pub fn expected(&self) -> Option<&'static str> {
+        self.expected
+    }
+
+    /// Type of the innermost value being validated, such as `BulkString`
+    #[inline]
+    pub fn type_name(&self) -> Option<&'static str> {
+        self.type_name
+    }
+
+    /// Index of the value within each enclosing aggregate, outermost first
+    ///
+    /// Keys and values of a Map are counted separately, as they appear on the wire.
+    /// Empty for incomplete input.
+    #[inline]
+    pub fn path(&self) -> &[usize] {
+        &self.path
+    }
+}
+
+impl PartialEq<RespErrorKind> for RespError {
+    fn eq(&self, other: &RespErrorKind) -> bool {
+        self.kind == *other
+    }
+}
+
+impl std::fmt::Display for RespError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} At offset {}", self.kind, self.offset)?;
+        if let Some(type_name) = self.type_name {
+            write!(f, " in {}", type_name)?;
+        }
+        if !self.path.is_empty() {
+            write!(f, " at ")?;
+            for index in &self.path {
+                write!(f, "[{}]", index)?;
+            }
+        }
+        if let Some(byte) = self.byte {
+            write!(f, ", found 0x{:02x}", byte)?;
+        }
+        if let Some(expected) = self.expected {
+            write!(f, ", expected {}", expected.escape_debug())?;
+        }
+        write!(f, ".")?;
+        if self.context_length > 0 {
+            write!(f, " Context:")?;
+            for (index, byte) in self.context[..self.context_length].iter().enumerate() {
+                if self.context_offset + index == self.offset {
+                    write!(f, " [{:02x}]", byte)?;
+                } else {
+                    write!(f, " {:02x}", byte)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for RespError {}
+
+/// Name of the type starting with `first_char`
+pub(crate) fn type_name(first_char: u8) -> Option<&'static str> {
+    match first_char {
+        0x2b => Some("SimpleString"),
+        0x2d => Some("Error"),
+        0x3a => Some("Integer"),
+        0x24 => Some("BulkString"),
+        0x2a => Some("Array"),
+        0x5f => Some("Null"),
+        0x23 => Some("Boolean"),
+        0x2c => Some("Double"),
+        0x28 => Some("BigNumber"),
+        0x25 => Some("Map"),
+        0x7e => Some("Set"),
+        0x3e => Some("Push"),
+        0x7c => Some("Attribute"),
+        0x21 => Some("BlobError"),
+        0x3d => Some("VerbatimString"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests_resp_error {
+    use crate::{RespError, RespErrorKind};
+
+    #[test]
+    fn test_new() {
+        let input = b"+OK\rx\n";
+        let error = RespError::new(RespErrorKind::InvalidTerminate, input, &input.len(), 4)
+            .expecting("\n")
+            .within(0x2b);
+        assert_eq!(error, RespErrorKind::InvalidTerminate);
+        assert_eq!(error.offset(), 4);
+        assert_eq!(error.byte(), Some(b'x'));
+        assert_eq!(error.expected(), Some("\n"));
+        assert_eq!(error.type_name(), Some("SimpleString"));
+        assert!(error.path().is_empty());
+        assert_eq!(
+            error.to_string(),
+            "Invalid terminate. At offset 4 in SimpleString, found 0x78, expected \\n. \
+             Context: 2b 4f 4b 0d [78] 0a"
+        );
+    }
+
+    #[test]
+    fn test_incomplete() {
+        let input = b"$3\r\nfoo\r\n";
+        let error = RespError::incomplete(Some(3), input, &6);
+        assert_eq!(error, RespErrorKind::Incomplete { needed: Some(3) });
+        assert_eq!(error.offset(), 6);
+        assert_eq!(error.byte(), None);
+        assert_eq!(
+            error.to_string(),
+            "Incomplete input, 3 more bytes needed. At offset 6. Context: 24 33 0d 0a 66 6f"
+        );
+    }
+
+    #[test]
+    fn test_nested_in_path() {
+        let input = b"+OK\n";
+        let error = RespError::new(RespErrorKind::InvalidTerminate, input, &input.len(), 3)
+            .within(0x2b)
+            .nested_in_path(vec![0])
+            .within(0x2a)
+            .nested_in_path(vec![3, 1])
+            .within(0x2a);
+        assert_eq!(error.path(), &[3, 1, 0]);
+        assert_eq!(error.type_name(), Some("SimpleString"));
+    }
+
+    #[test]
+    fn test_context_window() {
+        let input = [0x61; 32];
+        let error = RespError::new(RespErrorKind::InvalidValue, &input, &input.len(), 16);
+        assert_eq!(
+            error.to_string(),
+            "Invalid value. At offset 16, found 0x61. \
+             Context: 61 61 61 61 61 61 61 61 [61] 61 61 61 61 61 61 61 61"
+        );
+    }
+
+    #[test]
+    fn test_rebase() {
+        let input = b":12x4\r\n";
+        let error = RespError::new(RespErrorKind::InvalidInteger, &input[1..5], &4, 2).rebase(
+            input,
+            &input.len(),
+            1,
+        );
+        assert_eq!(error.offset(), 3);
+        assert_eq!(error.byte(), Some(b'x'));
+    }
+}
@@ -0,0 +1,703 @@
+use crate::{
+    aggregate, streamed, Array, Attribute, BigNumber, BlobError, Boolean, BulkString, Double,
+    Error, Integer, Limits, Map, Null, Push, RespError, RespErrorKind, RespType, Set, SimpleString,
+    VerbatimString,
+};
+use std::borrow::Cow;
+
+macro_rules! impl_ref {
+    ($($name:ident => $owned:ident),*) => {
+        $(
+            #[doc = concat!("Borrowed view of a [`", stringify!($owned), "`], over the input it was parsed from")]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            pub struct $name<'a>(&'a [u8]);
+
+            impl<'a> $name<'a> {
+                /// Encoded value, as sent
+                #[inline]
+                pub fn as_bytes(&self) -> &'a [u8] {
+                    self.0
+                }
+
+                #[allow(clippy::len_without_is_empty)]
+                #[inline]
+                pub fn len(&self) -> usize {
+                    self.0.len()
+                }
+
+                pub fn parse(
+                    input: &'a [u8],
+                    start: &mut usize,
+                    end: &usize,
+                ) -> Result<Self, RespError> {
+                    Self::parse_with(input, start, end, &Limits::unlimited())
+                }
+
+                /// Parse a value without copying it, rejecting input beyond `limits`
+                pub fn parse_with(
+                    input: &'a [u8],
+                    start: &mut usize,
+                    end: &usize,
+                    limits: &Limits,
+                ) -> Result<Self, RespError> {
+                    let mut index = *start;
+                    $owned::while_valid_with(input, &mut index, end, limits)?;
+                    let value = Self(&input[*start..index]);
+                    *start = index;
+                    Ok(value)
+                }
+
+                /// Copy into an owned value, reassembling it if it was streamed
+                #[inline]
+                pub fn to_owned(self) -> $owned {
+                    $owned::from_bytes_unchecked(streamed::reassemble(self.0))
+                }
+            }
+        )*
+    };
+}
+
+impl_ref!(
+    SimpleStringRef => SimpleString,
+    ErrorRef => Error,
+    IntegerRef => Integer,
+    BulkStringRef => BulkString,
+    ArrayRef => Array,
+    NullRef => Null,
+    BooleanRef => Boolean,
+    DoubleRef => Double,
+    BigNumberRef => BigNumber,
+    MapRef => Map,
+    SetRef => Set,
+    PushRef => Push,
+    AttributeRef => Attribute,
+    BlobErrorRef => BlobError,
+    VerbatimStringRef => VerbatimString
+);
+
+/// Value of a line type, between the type byte and CRLF
+#[inline]
+fn line_value(input: &[u8]) -> &[u8] {
+    &input[1..input.len() - 2]
+}
+
+/// Value of a blob type, between the CRLF ending its length and the final CRLF
+#[inline]
+fn blob_value(input: &[u8]) -> &[u8] {
+    let mut index = 1;
+    while input[index] != 0x0d {
+        index += 1;
+    }
+    &input[index + 2..input.len() - 2]
+}
+
+impl<'a> SimpleStringRef<'a> {
+    #[inline]
+    pub fn value(&self) -> &'a [u8] {
+        line_value(self.0)
+    }
+}
+
+impl<'a> ErrorRef<'a> {
+    #[inline]
+    pub fn value(&self) -> &'a [u8] {
+        line_value(self.0)
+    }
+}
+
+impl<'a> IntegerRef<'a> {
+    #[inline]
+    pub fn value(&self) -> i64 {
+        std::str::from_utf8(line_value(self.0))
+            .ok()
+            .and_then(|string| string.parse::<i64>().ok())
+            .expect("Integer holds a valid value")
+    }
+
+    #[inline]
+    pub fn raw_value(&self) -> &'a [u8] {
+        line_value(self.0)
+    }
+}
+
+impl<'a> BulkStringRef<'a> {
+    #[inline]
+    pub fn is_null(&self) -> bool {
+        self.0 == b"$-1\r\n"
+    }
+
+    /// Whether the value was sent in chunks, and can only be read by copying it
+    #[inline]
+    pub fn is_streamed(&self) -> bool {
+        self.0[1] == 0x3f
+    }
+
+    /// Value, or `None` for a Null Bulk String
+    ///
+    /// The value is borrowed from the input, unless it was streamed.
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::BulkStringRef;
+    /// use std::borrow::Cow;
+    ///
+    /// let string = b"$3\r\nfoo\r\n";
+    /// let bulk_string = BulkStringRef::parse(string, &mut 0, &string.len()).unwrap();
+    /// assert_eq!(bulk_string.value(), Some(Cow::Borrowed(&b"foo"[..])));
+    /// let string = b"$?\r\n;2\r\nfo\r\n;1\r\no\r\n;0\r\n";
+    /// let bulk_string = BulkStringRef::parse(string, &mut 0, &string.len()).unwrap();
+    /// assert_eq!(bulk_string.value(), Some(Cow::Owned(b"foo".to_vec())));
+    /// ```
+    pub fn value(&self) -> Option<Cow<'a, [u8]>> {
+        if self.is_null() {
+            return None;
+        }
+        if self.is_streamed() {
+            return Self::to_owned(*self).to_vec().map(Cow::Owned);
+        }
+        Some(Cow::Borrowed(blob_value(self.0)))
+    }
+}
+
+impl<'a> BooleanRef<'a> {
+    #[inline]
+    pub fn value(&self) -> bool {
+        self.0[1] == 0x74
+    }
+}
+
+impl<'a> DoubleRef<'a> {
+    #[inline]
+    pub fn value(&self) -> f64 {
+        std::str::from_utf8(line_value(self.0))
+            .ok()
+            .and_then(|string| string.parse::<f64>().ok())
+            .unwrap_or(f64::NAN)
+    }
+
+    #[inline]
+    pub fn raw_value(&self) -> &'a [u8] {
+        line_value(self.0)
+    }
+}
+
+impl<'a> BigNumberRef<'a> {
+    #[inline]
+    pub fn value(&self) -> &'a [u8] {
+        line_value(self.0)
+    }
+}
+
+impl<'a> BlobErrorRef<'a> {
+    #[inline]
+    pub fn value(&self) -> &'a [u8] {
+        blob_value(self.0)
+    }
+}
+
+impl<'a> VerbatimStringRef<'a> {
+    #[inline]
+    pub fn format(&self) -> &'a [u8] {
+        &blob_value(self.0)[..3]
+    }
+
+    #[inline]
+    pub fn value(&self) -> &'a [u8] {
+        &blob_value(self.0)[4..]
+    }
+}
+
+impl<'a> ArrayRef<'a> {
+    #[inline]
+    pub fn is_null(&self) -> bool {
+        self.0 == b"*-1\r\n"
+    }
+
+    /// Iterate over the elements, borrowing them from the input
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{ArrayRef, RespRef};
+    ///
+    /// let string = b"*2\r\n$3\r\nfoo\r\n:1\r\n";
+    /// let array = ArrayRef::parse(string, &mut 0, &string.len()).unwrap();
+    /// let mut iter = array.iter();
+    /// assert_eq!(iter.next().map(|value| value.as_bytes()), Some(&b"$3\r\nfoo\r\n"[..]));
+    /// assert!(matches!(iter.next(), Some(RespRef::Integer(integer)) if integer.value() == 1));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn iter(&self) -> ArrayRefIter<'a> {
+        ArrayRefIter::new(0x2a, self.0)
+    }
+
+    /// Number of elements, 0 for a Null Array
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Element at `index`, or `None` if it is out of bounds
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<RespRef<'a>> {
+        self.iter().nth(index)
+    }
+}
+
+impl<'a> IntoIterator for ArrayRef<'a> {
+    type Item = RespRef<'a>;
+    type IntoIter = ArrayRefIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> ArrayRefIter<'a> {
+        self.iter()
+    }
+}
+
+impl<'a> SetRef<'a> {
+    /// Iterate over the elements, borrowing them from the input
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::SetRef;
+    ///
+    /// let string = b"~2\r\n+a\r\n+b\r\n";
+    /// let set = SetRef::parse(string, &mut 0, &string.len()).unwrap();
+    /// let values: Vec<&[u8]> = set.iter().map(|value| value.as_bytes()).collect();
+    /// assert_eq!(values, vec![&b"+a\r\n"[..], b"+b\r\n"]);
+    /// ```
+    pub fn iter(&self) -> ArrayRefIter<'a> {
+        ArrayRefIter::new(0x7e, self.0)
+    }
+
+    /// Number of elements
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.iter().count()
+    }
+}
+
+impl<'a> IntoIterator for SetRef<'a> {
+    type Item = RespRef<'a>;
+    type IntoIter = ArrayRefIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> ArrayRefIter<'a> {
+        self.iter()
+    }
+}
+
+impl<'a> PushRef<'a> {
+    /// Iterate over the elements, borrowing them from the input
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{PushRef, RespRef};
+    ///
+    /// let string = b">2\r\n+message\r\n+hello\r\n";
+    /// let push = PushRef::parse(string, &mut 0, &string.len()).unwrap();
+    /// let mut iter = push.iter();
+    /// let kind = iter.next();
+    /// assert!(matches!(kind, Some(RespRef::SimpleString(kind)) if kind.value() == b"message"));
+    /// assert_eq!(iter.next().map(|value| value.as_bytes()), Some(&b"+hello\r\n"[..]));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn iter(&self) -> ArrayRefIter<'a> {
+        ArrayRefIter::new(0x3e, self.0)
+    }
+
+    /// Number of elements
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.iter().count()
+    }
+}
+
+impl<'a> IntoIterator for PushRef<'a> {
+    type Item = RespRef<'a>;
+    type IntoIter = ArrayRefIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> ArrayRefIter<'a> {
+        self.iter()
+    }
+}
+
+impl<'a> MapRef<'a> {
+    /// Iterate over the entries, borrowing keys and values from the input
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{MapRef, RespRef};
+    ///
+    /// let string = b"%1\r\n+ttl\r\n:3600\r\n";
+    /// let map = MapRef::parse(string, &mut 0, &string.len()).unwrap();
+    /// let mut iter = map.iter();
+    /// let (key, value) = iter.next().unwrap();
+    /// assert_eq!(key.as_bytes(), b"+ttl\r\n");
+    /// assert!(matches!(value, RespRef::Integer(integer) if integer.value() == 3600));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn iter(&self) -> MapRefIter<'a> {
+        MapRefIter(ArrayRefIter::new(0x25, self.0))
+    }
+
+    /// Number of entries
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.iter().count()
+    }
+}
+
+impl<'a> IntoIterator for MapRef<'a> {
+    type Item = (RespRef<'a>, RespRef<'a>);
+    type IntoIter = MapRefIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> MapRefIter<'a> {
+        self.iter()
+    }
+}
+
+/// Iterator over the entries of a [`MapRef`]
+#[derive(Debug, Clone)]
+pub struct MapRefIter<'a>(ArrayRefIter<'a>);
+
+impl<'a> Iterator for MapRefIter<'a> {
+    type Item = (RespRef<'a>, RespRef<'a>);
+
+    fn next(&mut self) -> Option<(RespRef<'a>, RespRef<'a>)> {
+        let key = self.0.next()?;
+        let value = self.0.next().expect("Map holds a value for each key");
+        Some((key, value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.0.size_hint();
+        (lower / 2, upper.map(|upper| upper / 2))
+    }
+}
+
+/// Iterator over the elements of an [`ArrayRef`], a [`SetRef`] or a [`PushRef`]
+#[derive(Debug, Clone)]
+pub struct ArrayRefIter<'a> {
+    input: &'a [u8],
+    index: usize,
+    /// Elements left, `None` for a streamed aggregate.
+    remaining: Option<usize>,
+}
+
+impl<'a> ArrayRefIter<'a> {
+    /// Iterate over the values of the validated aggregate `input`, keys and values alike for a Map
+    pub(crate) fn new(first_char: u8, input: &'a [u8]) -> Self {
+        let mut index = 0;
+        let remaining = aggregate::while_valid_header(
+            first_char,
+            input,
+            &mut index,
+            &input.len(),
+            &Limits::unlimited(),
+        )
+        .expect("aggregate holds a valid header");
+        Self {
+            input,
+            index,
+            remaining,
+        }
+    }
+}
+
+impl<'a> Iterator for ArrayRefIter<'a> {
+    type Item = RespRef<'a>;
+
+    fn next(&mut self) -> Option<RespRef<'a>> {
+        match self.remaining {
+            Some(0) => return None,
+            Some(ref mut remaining) => *remaining -= 1,
+            None if self.input[self.index] == 0x2e => return None,
+            None => {}
+        }
+        let value = RespRef::parse_with(
+            self.input,
+            &mut self.index,
+            &self.input.len(),
+            &Limits::unlimited(),
+        )
+        .expect("aggregate holds valid elements");
+        Some(value)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.remaining {
+            Some(remaining) => (remaining, Some(remaining)),
+            None => (0, None),
+        }
+    }
+}
+
+/// Borrowed view of any RESP value, over the input it was parsed from
+///
+/// Parsing only validates the input and records where the value lies in it,
+/// so nothing is copied until [`RespRef::to_owned`] is called.
+///
+/// # Example
+/// ``` rust
+/// use resp_protocol::{RespRef, RespType, SimpleString};
+///
+/// let string = b"+OK\r\n";
+/// let value = RespRef::parse(string, &mut 0, &string.len()).unwrap();
+/// assert_eq!(value.as_bytes().as_ptr(), string.as_ptr());
+/// assert_eq!(value.to_owned(), RespType::SimpleString(SimpleString::new(b"OK")));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RespRef<'a> {
+    SimpleString(SimpleStringRef<'a>),
+    Error(ErrorRef<'a>),
+    Integer(IntegerRef<'a>),
+    BulkString(BulkStringRef<'a>),
+    Array(ArrayRef<'a>),
+    Null(NullRef<'a>),
+    Boolean(BooleanRef<'a>),
+    Double(DoubleRef<'a>),
+    BigNumber(BigNumberRef<'a>),
+    Map(MapRef<'a>),
+    Set(SetRef<'a>),
+    Push(PushRef<'a>),
+    Attribute(AttributeRef<'a>),
+    BlobError(BlobErrorRef<'a>),
+    VerbatimString(VerbatimStringRef<'a>),
+}
+
+impl<'a> RespRef<'a> {
+    /// Encoded value, as sent
+    #[inline]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        match self {
+            RespRef::SimpleString(simple_string) => simple_string.as_bytes(),
+            RespRef::Error(error) => error.as_bytes(),
+            RespRef::Integer(integer) => integer.as_bytes(),
+            RespRef::BulkString(bulk_string) => bulk_string.as_bytes(),
+            RespRef::Array(array) => array.as_bytes(),
+            RespRef::Null(null) => null.as_bytes(),
+            RespRef::Boolean(boolean) => boolean.as_bytes(),
+            RespRef::Double(double) => double.as_bytes(),
+            RespRef::BigNumber(big_number) => big_number.as_bytes(),
+            RespRef::Map(map) => map.as_bytes(),
+            RespRef::Set(set) => set.as_bytes(),
+            RespRef::Push(push) => push.as_bytes(),
+            RespRef::Attribute(attribute) => attribute.as_bytes(),
+            RespRef::BlobError(blob_error) => blob_error.as_bytes(),
+            RespRef::VerbatimString(verbatim_string) => verbatim_string.as_bytes(),
+        }
+    }
+
+    #[allow(clippy::len_without_is_empty)]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.as_bytes().len()
+    }
+
+    /// Wrap a single, already validated value, dispatching on its type byte
+    ///
+    /// # Panics
+    ///
+    /// Panics if `input` is empty or starts with an unknown type byte.
+    pub fn from_slice_unchecked(input: &'a [u8]) -> Self {
+        match input[0] {
+            0x2b => RespRef::SimpleString(SimpleStringRef(input)),
+            0x2d => RespRef::Error(ErrorRef(input)),
+            0x3a => RespRef::Integer(IntegerRef(input)),
+            0x24 => RespRef::BulkString(BulkStringRef(input)),
+            0x2a => RespRef::Array(ArrayRef(input)),
+            0x5f => RespRef::Null(NullRef(input)),
+            0x23 => RespRef::Boolean(BooleanRef(input)),
+            0x2c => RespRef::Double(DoubleRef(input)),
+            0x28 => RespRef::BigNumber(BigNumberRef(input)),
+            0x25 => RespRef::Map(MapRef(input)),
+            0x7e => RespRef::Set(SetRef(input)),
+            0x3e => RespRef::Push(PushRef(input)),
+            0x7c => RespRef::Attribute(AttributeRef(input)),
+            0x21 => RespRef::BlobError(BlobErrorRef(input)),
+            0x3d => RespRef::VerbatimString(VerbatimStringRef(input)),
+            _ => unreachable!("validated value has an unknown type byte"),
+        }
+    }
+
+    pub fn parse(input: &'a [u8], start: &mut usize, end: &usize) -> Result<Self, RespError> {
+        Self::parse_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Parse any RESP value without copying it, rejecting input beyond `limits`
+    pub fn parse_with(
+        input: &'a [u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        if *start >= *end {
+            return Err(RespError::incomplete(None, input, end));
+        }
+        match input[*start] {
+            0x2b => {
+                SimpleStringRef::parse_with(input, start, end, limits).map(RespRef::SimpleString)
+            }
+            0x2d => ErrorRef::parse_with(input, start, end, limits).map(RespRef::Error),
+            0x3a => IntegerRef::parse_with(input, start, end, limits).map(RespRef::Integer),
+            0x24 => BulkStringRef::parse_with(input, start, end, limits).map(RespRef::BulkString),
+            0x2a => ArrayRef::parse_with(input, start, end, limits).map(RespRef::Array),
+            0x5f => NullRef::parse_with(input, start, end, limits).map(RespRef::Null),
+            0x23 => BooleanRef::parse_with(input, start, end, limits).map(RespRef::Boolean),
+            0x2c => DoubleRef::parse_with(input, start, end, limits).map(RespRef::Double),
+            0x28 => BigNumberRef::parse_with(input, start, end, limits).map(RespRef::BigNumber),
+            0x25 => MapRef::parse_with(input, start, end, limits).map(RespRef::Map),
+            0x7e => SetRef::parse_with(input, start, end, limits).map(RespRef::Set),
+            0x3e => PushRef::parse_with(input, start, end, limits).map(RespRef::Push),
+            0x7c => AttributeRef::parse_with(input, start, end, limits).map(RespRef::Attribute),
+            0x21 => BlobErrorRef::parse_with(input, start, end, limits).map(RespRef::BlobError),
+            0x3d => VerbatimStringRef::parse_with(input, start, end, limits)
+                .map(RespRef::VerbatimString),
+            _ => Err(RespError::new(
+                RespErrorKind::InvalidFirstChar,
+                input,
+                end,
+                *start,
+            )),
+        }
+    }
+
+    /// Copy into an owned value, reassembling it if it was streamed
+    #[inline]
+    pub fn to_owned(self) -> RespType {
+        RespType::from_bytes_unchecked(streamed::reassemble(self.as_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests_resp_ref {
+    use crate::{
+        ArrayBuilder, ArrayRef, BulkString, BulkStringRef, Integer, MapRef, PushRef, RespErrorKind,
+        RespRef, RespType, SetRef, SimpleString, VerbatimStringRef,
+    };
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_parse_is_zero_copy() {
+        let string = b"+OK\r\n$3\r\nfoo\r\n";
+        let mut cursor = 0;
+        let value = RespRef::parse(string, &mut cursor, &string.len()).unwrap();
+        assert!(
+            matches!(value, RespRef::SimpleString(simple_string) if simple_string.value() == b"OK")
+        );
+        assert_eq!(value.as_bytes().as_ptr(), string.as_ptr());
+        let value = RespRef::parse(string, &mut cursor, &string.len()).unwrap();
+        assert_eq!(value.as_bytes(), b"$3\r\nfoo\r\n");
+        assert_eq!(value.as_bytes().as_ptr(), string[5..].as_ptr());
+        assert_eq!(cursor, string.len());
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let string = b"*2\r\n:1\r\n";
+        assert_eq!(
+            RespRef::parse(string, &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: None }
+        );
+        assert_eq!(
+            RespRef::parse(b"?\r\n", &mut 0, &3).unwrap_err(),
+            RespErrorKind::InvalidFirstChar
+        );
+    }
+
+    #[test]
+    fn test_to_owned() {
+        let string = b"*2\r\n:1\r\n$?\r\n;2\r\nfo\r\n;1\r\no\r\n;0\r\n";
+        let value = RespRef::parse(string, &mut 0, &string.len()).unwrap();
+        let array = ArrayBuilder::new()
+            .insert(RespType::Integer(Integer::new(1)))
+            .insert(RespType::BulkString(BulkString::new(b"foo")))
+            .build();
+        assert_eq!(value.to_owned(), RespType::Array(array));
+        let string = b"+OK\r\n";
+        let value = RespRef::parse(string, &mut 0, &string.len()).unwrap();
+        assert_eq!(
+            value.to_owned(),
+            RespType::SimpleString(SimpleString::new(b"OK"))
+        );
+    }
+
+    #[test]
+    fn test_bulk_string_value() {
+        let string = b"$3\r\nfoo\r\n";
+        let bulk_string = BulkStringRef::parse(string, &mut 0, &string.len()).unwrap();
+        assert!(matches!(bulk_string.value(), Some(Cow::Borrowed(value)) if value == b"foo"));
+        let string = b"$-1\r\n";
+        let bulk_string = BulkStringRef::parse(string, &mut 0, &string.len()).unwrap();
+        assert!(bulk_string.is_null());
+        assert_eq!(bulk_string.value(), None);
+    }
+
+    #[test]
+    fn test_verbatim_string_value() {
+        let string = b"=7\r\ntxt:foo\r\n";
+        let verbatim_string = VerbatimStringRef::parse(string, &mut 0, &string.len()).unwrap();
+        assert_eq!(verbatim_string.format(), b"txt");
+        assert_eq!(verbatim_string.value(), b"foo");
+    }
+
+    #[test]
+    fn test_array_iter() {
+        let string = b"*3\r\n:1\r\n*1\r\n+a\r\n$-1\r\n";
+        let array = ArrayRef::parse(string, &mut 0, &string.len()).unwrap();
+        assert_eq!(array.count(), 3);
+        let values: Vec<&[u8]> = array.iter().map(|value| value.as_bytes()).collect();
+        assert_eq!(values, vec![&b":1\r\n"[..], b"*1\r\n+a\r\n", b"$-1\r\n"]);
+        assert!(matches!(array.get(0), Some(RespRef::Integer(integer)) if integer.value() == 1));
+        assert_eq!(array.get(3), None);
+        let string = b"*?\r\n:1\r\n:2\r\n.\r\n";
+        let array = ArrayRef::parse(string, &mut 0, &string.len()).unwrap();
+        assert_eq!(array.count(), 2);
+        let string = b"*-1\r\n";
+        let array = ArrayRef::parse(string, &mut 0, &string.len()).unwrap();
+        assert!(array.is_null());
+        assert_eq!(array.count(), 0);
+    }
+
+    #[test]
+    fn test_set_and_push_iter() {
+        let string = b"~?\r\n+a\r\n:1\r\n.\r\n";
+        let set = SetRef::parse(string, &mut 0, &string.len()).unwrap();
+        assert_eq!(set.count(), 2);
+        let values: Vec<&[u8]> = set.into_iter().map(|value| value.as_bytes()).collect();
+        assert_eq!(values, vec![&b"+a\r\n"[..], b":1\r\n"]);
+        let string = b">2\r\n+message\r\n*1\r\n+a\r\n";
+        let push = PushRef::parse(string, &mut 0, &string.len()).unwrap();
+        assert_eq!(push.count(), 2);
+        assert_eq!(
+            push.iter().nth(1).map(|value| value.as_bytes()),
+            Some(&b"*1\r\n+a\r\n"[..])
+        );
+    }
+
+    #[test]
+    fn test_map_iter() {
+        let string = b"%?\r\n+a\r\n:1\r\n+b\r\n*1\r\n:2\r\n.\r\n";
+        let map = MapRef::parse(string, &mut 0, &string.len()).unwrap();
+        assert_eq!(map.count(), 2);
+        let entries: Vec<(&[u8], &[u8])> = map
+            .iter()
+            .map(|(key, value)| (key.as_bytes(), value.as_bytes()))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                (&b"+a\r\n"[..], &b":1\r\n"[..]),
+                (&b"+b\r\n"[..], &b"*1\r\n:2\r\n"[..])
+            ]
+        );
+        let string = b"%0\r\n";
+        let map = MapRef::parse(string, &mut 0, &string.len()).unwrap();
+        assert_eq!(map.iter().size_hint(), (0, Some(0)));
+        assert_eq!(map.into_iter().next(), None);
+    }
+}
@@ -0,0 +1,593 @@
+//! Serialization of any `Serialize` value into RESP
+//!
+//! | Rust                                   | RESP3                | RESP2                      |
+//! |----------------------------------------|----------------------|----------------------------|
+//! | `bool`                                 | Boolean              | Integer `0` or `1`         |
+//! | integers                               | Integer              | Integer                    |
+//! | integers beyond `i64`                  | Big number           | Bulk String                |
+//! | floats                                 | Double               | Bulk String                |
+//! | strings, chars, bytes                  | Bulk String          | Bulk String                |
+//! | `None`, `()`, unit structs             | Null                 | Null Bulk String           |
+//! | sequences, tuples                      | Array                | Array                      |
+//! | maps, structs                          | Map                  | Array of keys and values   |
+//! | unit variants                          | Bulk String          | Bulk String                |
+//! | other variants                         | Map of one entry     | Array of name and value    |
+//!
+//! Struct fields and variants are keyed by their names, as Bulk Strings.
+
+use crate::{to_resp, Boolean, BulkString, Double, Integer, Protocol, RespType, ToRespArg};
+use serde::ser::{self, Serialize};
+use std::convert::TryFrom;
+
+/// Serialize `value` into RESP3
+///
+/// # Example
+/// ``` rust
+/// use resp_protocol::{ser, RespType};
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct User {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// let user = User { name: "foo".to_owned(), age: 42 };
+/// let value: RespType = ser::to_resp(&user).unwrap();
+/// assert_eq!(
+///     value.bytes(),
+///     &b"%2\r\n$4\r\nname\r\n$3\r\nfoo\r\n$3\r\nage\r\n:42\r\n"[..]
+/// );
+/// ```
+#[inline]
+pub fn to_resp<T: Serialize + ?Sized>(value: &T) -> Result<RespType, SerializeError> {
+    to_resp_with(value, Protocol::Resp3)
+}
+
+/// Serialize `value` into the given version of the protocol
+///
+/// # Example
+/// ``` rust
+/// use resp_protocol::{ser, Protocol};
+/// use std::collections::BTreeMap;
+///
+/// let mut map = BTreeMap::new();
+/// map.insert("a", 1.5);
+/// let value = ser::to_resp_with(&map, Protocol::Resp2).unwrap();
+/// assert_eq!(value.bytes(), &b"*2\r\n$1\r\na\r\n$3\r\n1.5\r\n"[..]);
+/// ```
+#[inline]
+pub fn to_resp_with<T: Serialize + ?Sized>(
+    value: &T,
+    protocol: Protocol,
+) -> Result<RespType, SerializeError> {
+    value.serialize(Serializer::new(protocol))
+}
+
+/// Error raised by a `Serialize` implementation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerializeError(String);
+
+impl std::fmt::Display for SerializeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for SerializeError {}
+
+impl ser::Error for SerializeError {
+    fn custom<T: std::fmt::Display>(message: T) -> Self {
+        Self(message.to_string())
+    }
+}
+
+/// Serializer into a [`RespType`]
+#[derive(Debug, Clone, Copy)]
+pub struct Serializer {
+    protocol: Protocol,
+}
+
+impl Serializer {
+    #[inline]
+    pub fn new(protocol: Protocol) -> Self {
+        Self { protocol }
+    }
+
+    /// Value of an enum variant, keyed by its name
+    fn variant(self, name: &'static str, value: RespType) -> RespType {
+        to_resp::map(self.protocol, Some((bulk_string(name.as_bytes()), value)))
+    }
+}
+
+#[inline]
+fn bulk_string(value: &[u8]) -> RespType {
+    RespType::BulkString(BulkString::new(value))
+}
+
+impl ser::Serializer for Serializer {
+    type Ok = RespType;
+    type Error = SerializeError;
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = SeqSerializer;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = MapSerializer;
+
+    fn serialize_bool(self, value: bool) -> Result<RespType, SerializeError> {
+        Ok(match self.protocol {
+            Protocol::Resp2 => RespType::Integer(Integer::new(value as i64)),
+            Protocol::Resp3 => RespType::Boolean(Boolean::new(value)),
+        })
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<RespType, SerializeError> {
+        self.serialize_i64(value as i64)
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<RespType, SerializeError> {
+        self.serialize_i64(value as i64)
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<RespType, SerializeError> {
+        self.serialize_i64(value as i64)
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<RespType, SerializeError> {
+        Ok(RespType::Integer(Integer::new(value)))
+    }
+
+    fn serialize_i128(self, value: i128) -> Result<RespType, SerializeError> {
+        match i64::try_from(value) {
+            Ok(value) => self.serialize_i64(value),
+            Err(_) => Ok(to_resp::big_number(self.protocol, &value.to_string())),
+        }
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<RespType, SerializeError> {
+        self.serialize_i64(value as i64)
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<RespType, SerializeError> {
+        self.serialize_i64(value as i64)
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<RespType, SerializeError> {
+        self.serialize_i64(value as i64)
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<RespType, SerializeError> {
+        self.serialize_u128(value as u128)
+    }
+
+    fn serialize_u128(self, value: u128) -> Result<RespType, SerializeError> {
+        match i64::try_from(value) {
+            Ok(value) => self.serialize_i64(value),
+            Err(_) => Ok(to_resp::big_number(self.protocol, &value.to_string())),
+        }
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<RespType, SerializeError> {
+        match self.protocol {
+            Protocol::Resp2 => Ok(bulk_string(&value.to_resp_arg())),
+            Protocol::Resp3 => Ok(RespType::Double(Double::from_float(value))),
+        }
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<RespType, SerializeError> {
+        Ok(match self.protocol {
+            Protocol::Resp2 => bulk_string(&value.to_resp_arg()),
+            Protocol::Resp3 => RespType::Double(Double::new(value)),
+        })
+    }
+
+    fn serialize_char(self, value: char) -> Result<RespType, SerializeError> {
+        self.serialize_str(value.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, value: &str) -> Result<RespType, SerializeError> {
+        Ok(bulk_string(value.as_bytes()))
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<RespType, SerializeError> {
+        Ok(bulk_string(value))
+    }
+
+    fn serialize_none(self) -> Result<RespType, SerializeError> {
+        Ok(to_resp::null(self.protocol))
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<RespType, SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<RespType, SerializeError> {
+        Ok(to_resp::null(self.protocol))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<RespType, SerializeError> {
+        Ok(to_resp::null(self.protocol))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<RespType, SerializeError> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<RespType, SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<RespType, SerializeError> {
+        Ok(self.variant(variant, value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, length: Option<usize>) -> Result<SeqSerializer, SerializeError> {
+        Ok(SeqSerializer::new(self, None, length.unwrap_or(0)))
+    }
+
+    fn serialize_tuple(self, length: usize) -> Result<SeqSerializer, SerializeError> {
+        Ok(SeqSerializer::new(self, None, length))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        length: usize,
+    ) -> Result<SeqSerializer, SerializeError> {
+        Ok(SeqSerializer::new(self, None, length))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        length: usize,
+    ) -> Result<SeqSerializer, SerializeError> {
+        Ok(SeqSerializer::new(self, Some(variant), length))
+    }
+
+    fn serialize_map(self, length: Option<usize>) -> Result<MapSerializer, SerializeError> {
+        Ok(MapSerializer::new(self, None, length.unwrap_or(0)))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        length: usize,
+    ) -> Result<MapSerializer, SerializeError> {
+        Ok(MapSerializer::new(self, None, length))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        length: usize,
+    ) -> Result<MapSerializer, SerializeError> {
+        Ok(MapSerializer::new(self, Some(variant), length))
+    }
+}
+
+/// Serializer of sequences and tuples into an Array
+#[derive(Debug)]
+pub struct SeqSerializer {
+    serializer: Serializer,
+    variant: Option<&'static str>,
+    values: Vec<RespType>,
+}
+
+impl SeqSerializer {
+    fn new(serializer: Serializer, variant: Option<&'static str>, length: usize) -> Self {
+        Self {
+            serializer,
+            variant,
+            values: Vec::with_capacity(length),
+        }
+    }
+
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        self.values.push(value.serialize(self.serializer)?);
+        Ok(())
+    }
+
+    fn build(self) -> Result<RespType, SerializeError> {
+        let value = to_resp::array(self.values);
+        Ok(match self.variant {
+            Some(variant) => self.serializer.variant(variant, value),
+            None => value,
+        })
+    }
+}
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = RespType;
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<RespType, SerializeError> {
+        self.build()
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer {
+    type Ok = RespType;
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<RespType, SerializeError> {
+        self.build()
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer {
+    type Ok = RespType;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<RespType, SerializeError> {
+        self.build()
+    }
+}
+
+impl ser::SerializeTupleVariant for SeqSerializer {
+    type Ok = RespType;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<RespType, SerializeError> {
+        self.build()
+    }
+}
+
+/// Serializer of maps and structs into a Map, or an Array of keys and values in RESP2
+#[derive(Debug)]
+pub struct MapSerializer {
+    serializer: Serializer,
+    variant: Option<&'static str>,
+    entries: Vec<(RespType, RespType)>,
+    key: Option<RespType>,
+}
+
+impl MapSerializer {
+    fn new(serializer: Serializer, variant: Option<&'static str>, length: usize) -> Self {
+        Self {
+            serializer,
+            variant,
+            entries: Vec::with_capacity(length),
+            key: None,
+        }
+    }
+
+    fn push_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        let value = value.serialize(self.serializer)?;
+        self.entries.push((bulk_string(key.as_bytes()), value));
+        Ok(())
+    }
+
+    fn build(self) -> Result<RespType, SerializeError> {
+        let value = to_resp::map(self.serializer.protocol, self.entries);
+        Ok(match self.variant {
+            Some(variant) => self.serializer.variant(variant, value),
+            None => value,
+        })
+    }
+}
+
+impl ser::SerializeMap for MapSerializer {
+    type Ok = RespType;
+    type Error = SerializeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerializeError> {
+        self.key = Some(key.serialize(self.serializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| ser::Error::custom("serialize_value called before serialize_key"))?;
+        let value = value.serialize(self.serializer)?;
+        self.entries.push((key, value));
+        Ok(())
+    }
+
+    fn end(self) -> Result<RespType, SerializeError> {
+        self.build()
+    }
+}
+
+impl ser::SerializeStruct for MapSerializer {
+    type Ok = RespType;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.push_field(key, value)
+    }
+
+    fn end(self) -> Result<RespType, SerializeError> {
+        self.build()
+    }
+}
+
+impl ser::SerializeStructVariant for MapSerializer {
+    type Ok = RespType;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.push_field(key, value)
+    }
+
+    fn end(self) -> Result<RespType, SerializeError> {
+        self.build()
+    }
+}
+
+#[cfg(test)]
+mod tests_ser {
+    use crate::ser::{to_resp, to_resp_with};
+    use crate::Protocol;
+    use bytes::Bytes;
+    use serde::Serialize;
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize)]
+    struct Entry {
+        id: u64,
+        score: f64,
+        tags: Vec<&'static str>,
+        ttl: Option<i32>,
+        #[serde(skip)]
+        #[allow(dead_code)]
+        cache: bool,
+    }
+
+    #[derive(Serialize)]
+    enum Event {
+        Ping,
+        Moved(u16),
+        Pair(i64, bool),
+        Set { key: &'static str },
+    }
+
+    fn bytes<T: Serialize>(value: &T, protocol: Protocol) -> Bytes {
+        to_resp_with(value, protocol).unwrap().bytes()
+    }
+
+    #[test]
+    fn test_struct() {
+        let entry = Entry {
+            id: 1,
+            score: 0.5,
+            tags: vec!["a"],
+            ttl: None,
+            cache: true,
+        };
+        assert_eq!(
+            bytes(&entry, Protocol::Resp3),
+            Bytes::from_static(
+                b"%4\r\n$2\r\nid\r\n:1\r\n$5\r\nscore\r\n,0.5\r\n\
+                  $4\r\ntags\r\n*1\r\n$1\r\na\r\n$3\r\nttl\r\n_\r\n"
+            )
+        );
+        assert_eq!(
+            bytes(&entry, Protocol::Resp2),
+            Bytes::from_static(
+                b"*8\r\n$2\r\nid\r\n:1\r\n$5\r\nscore\r\n$3\r\n0.5\r\n\
+                  $4\r\ntags\r\n*1\r\n$1\r\na\r\n$3\r\nttl\r\n$-1\r\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_enum() {
+        let events = vec![
+            Event::Ping,
+            Event::Moved(7000),
+            Event::Pair(-1, true),
+            Event::Set { key: "k" },
+        ];
+        assert_eq!(
+            bytes(&events, Protocol::Resp3),
+            Bytes::from_static(
+                b"*4\r\n$4\r\nPing\r\n%1\r\n$5\r\nMoved\r\n:7000\r\n\
+                  %1\r\n$4\r\nPair\r\n*2\r\n:-1\r\n#t\r\n\
+                  %1\r\n$3\r\nSet\r\n%1\r\n$3\r\nkey\r\n$1\r\nk\r\n"
+            )
+        );
+        assert_eq!(
+            bytes(&Event::Pair(-1, true), Protocol::Resp2),
+            Bytes::from_static(b"*2\r\n$4\r\nPair\r\n*2\r\n:-1\r\n:1\r\n")
+        );
+    }
+
+    #[test]
+    fn test_scalars() {
+        assert_eq!(
+            to_resp(&u64::MAX).unwrap().bytes(),
+            Bytes::from_static(b"(18446744073709551615\r\n")
+        );
+        assert_eq!(
+            bytes(&i128::MIN, Protocol::Resp2),
+            Bytes::from_static(b"$40\r\n-170141183460469231731687303715884105728\r\n")
+        );
+        assert_eq!(
+            bytes(&f64::INFINITY, Protocol::Resp2),
+            Bytes::from_static(b"$3\r\ninf\r\n")
+        );
+        assert_eq!(
+            to_resp(&1.1_f32).unwrap().bytes(),
+            Bytes::from_static(b",1.1\r\n")
+        );
+        assert_eq!(
+            bytes(&1.1_f32, Protocol::Resp2),
+            Bytes::from_static(b"$3\r\n1.1\r\n")
+        );
+        assert_eq!(to_resp(&'é').unwrap().bytes(), Bytes::from("$2\r\né\r\n"));
+        assert_eq!(to_resp(&()).unwrap().bytes(), Bytes::from_static(b"_\r\n"));
+    }
+
+    #[test]
+    fn test_map() {
+        let mut map = BTreeMap::new();
+        map.insert(1, "a");
+        map.insert(2, "b");
+        assert_eq!(
+            bytes(&map, Protocol::Resp3),
+            Bytes::from_static(b"%2\r\n:1\r\n$1\r\na\r\n:2\r\n$1\r\nb\r\n")
+        );
+        assert_eq!(
+            bytes(&map, Protocol::Resp2),
+            Bytes::from_static(b"*4\r\n:1\r\n$1\r\na\r\n:2\r\n$1\r\nb\r\n")
+        );
+    }
+}
@@ -0,0 +1,273 @@
+use crate::{aggregate, streamed, Limits, RespError, RespErrorKind, RespType};
+use bytes::{BufMut, Bytes, BytesMut};
+use std::convert::TryFrom;
+
+pub const EMPTY_SET: Set = Set(Bytes::from_static(b"~0\r\n"));
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Set(pub(crate) Bytes);
+
+/// Set type (RESP3)
+impl Set {
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    pub fn bytes(&self) -> Bytes {
+        self.0.clone()
+    }
+
+    #[inline]
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self == EMPTY_SET
+    }
+
+    #[inline]
+    pub fn from_bytes_unchecked(input: Bytes) -> Self {
+        Self(input)
+    }
+
+    #[inline]
+    pub fn from_slice_unchecked(input: &[u8]) -> Self {
+        let bytes = Bytes::copy_from_slice(input);
+        Self::from_bytes_unchecked(bytes)
+    }
+
+    /// Build a new Set from `input`, checking that it holds exactly one valid value
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{Set, RespErrorKind};
+    /// use bytes::Bytes;
+    ///
+    /// assert!(Set::try_from_bytes(Bytes::from_static(b"~1\r\n:1\r\n")).is_ok());
+    /// assert_eq!(
+    ///     Set::try_from_bytes(Bytes::from_static(b"~1\r\n:1\r\n\r\n")).unwrap_err(),
+    ///     RespErrorKind::TrailingBytes
+    /// );
+    /// ```
+    pub fn try_from_bytes(input: Bytes) -> Result<Self, RespError> {
+        let mut index = 0;
+        let is_streamed =
+            Self::while_valid_streamed(&input, &mut index, &input.len(), &Limits::unlimited())?;
+        if index != input.len() {
+            return Err(RespError::new(
+                RespErrorKind::TrailingBytes,
+                &input,
+                &input.len(),
+                index,
+            ));
+        }
+        Ok(Self::from_bytes_unchecked(streamed::reassemble_bytes(
+            input,
+            is_streamed,
+        )))
+    }
+
+    #[inline]
+    pub fn try_from_slice(input: &[u8]) -> Result<Self, RespError> {
+        Self::try_from_bytes(Bytes::copy_from_slice(input))
+    }
+
+    pub fn while_valid(input: &[u8], start: &mut usize, end: &usize) -> Result<(), RespError> {
+        Self::while_valid_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Validate a Set, whose nesting, element counts and nested values are bound by `limits`
+    pub fn while_valid_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<(), RespError> {
+        Self::while_valid_streamed(input, start, end, limits)?;
+        Ok(())
+    }
+
+    /// Validate a Set like [`Set::while_valid_with`], returning whether it holds
+    /// streamed values
+    pub(crate) fn while_valid_streamed(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<bool, RespError> {
+        limits.while_valid_frame(input, start, end, |input, start, end| {
+            aggregate::while_valid(0x7e, input, start, end, limits, 1)
+        })
+    }
+
+    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, RespError> {
+        Self::parse_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Parse a Set, whose nesting, element counts and nested values are bound by `limits`
+    pub fn parse_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        let is_streamed = Self::while_valid_streamed(input, &mut index, end, limits)?;
+        let value = Self::from_bytes_unchecked(streamed::copy(&input[*start..index], is_streamed));
+        *start = index;
+        Ok(value)
+    }
+
+    /// Parse a Set from `input`, sharing its buffer instead of copying it
+    ///
+    /// A streamed Set, or any streamed value within it, is reassembled into a new buffer instead.
+    pub fn parse_bytes(input: &Bytes, start: &mut usize) -> Result<Self, RespError> {
+        Self::parse_bytes_with(input, start, &Limits::unlimited())
+    }
+
+    /// Parse a Set from `input` like [`Set::parse_bytes`], whose nesting, element counts and nested
+    /// values are bound by `limits`
+    pub fn parse_bytes_with(
+        input: &Bytes,
+        start: &mut usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        let is_streamed = Self::while_valid_streamed(input, &mut index, &input.len(), limits)?;
+        let value = Self::from_bytes_unchecked(streamed::reassemble_bytes(
+            input.slice(*start..index),
+            is_streamed,
+        ));
+        *start = index;
+        Ok(value)
+    }
+}
+
+impl TryFrom<Bytes> for Set {
+    type Error = RespError;
+
+    fn try_from(input: Bytes) -> Result<Self, RespError> {
+        Self::try_from_bytes(input)
+    }
+}
+
+impl PartialEq<Set> for &Set {
+    fn eq(&self, other: &Set) -> bool {
+        self.0 == other.bytes()
+    }
+}
+
+pub struct SetBuilder {
+    inner: Vec<RespType>,
+}
+
+impl Default for SetBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SetBuilder {
+    /// Build a new Set Builder
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{Set, SetBuilder};
+    ///
+    /// let set_builder: SetBuilder = SetBuilder::new();
+    /// let set: Set = set_builder.build();
+    /// ```
+    #[inline]
+    pub fn new() -> SetBuilder {
+        SetBuilder {
+            inner: Vec::<RespType>::new(),
+        }
+    }
+
+    #[inline]
+    pub fn value(&mut self) -> Vec<RespType> {
+        self.inner.clone()
+    }
+
+    /// Add a new value to Set Builder
+    ///
+    /// # Example
+    /// ```rust
+    /// use resp_protocol::{RespType, Set, SetBuilder, SimpleString};
+    ///
+    /// let mut set_builder: SetBuilder = SetBuilder::new();
+    ///
+    /// set_builder.insert(RespType::SimpleString(SimpleString::new(b"OK")));
+    ///
+    /// let set: Set = set_builder.build();
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, value: RespType) -> &mut Self {
+        self.inner.push(value);
+        self
+    }
+
+    #[inline]
+    pub fn build(&self) -> Set {
+        let length = self.inner.len();
+        if length == 0 {
+            return EMPTY_SET;
+        }
+        let length_string = length.to_string();
+        let mut total_bytes = length_string.len() + 3;
+        for element in &self.inner {
+            total_bytes += element.len();
+        }
+        let mut bytes = BytesMut::with_capacity(total_bytes);
+        bytes.put_u8(0x7e); // "~"
+        bytes.put_slice(length_string.as_bytes());
+        bytes.put_u8(0x0d); // CR
+        bytes.put_u8(0x0a); // LF
+        for element in &self.inner {
+            bytes.put(element.bytes());
+        }
+        Set(bytes.freeze())
+    }
+}
+
+#[cfg(test)]
+mod tests_set {
+    use crate::{Integer, RespErrorKind, RespType, Set, SetBuilder, SimpleString, EMPTY_SET};
+    use bytes::Bytes;
+
+    #[test]
+    fn test_build_empty_set() {
+        assert_eq!(SetBuilder::new().build(), EMPTY_SET);
+    }
+
+    #[test]
+    fn test_build_set() {
+        let set = SetBuilder::new()
+            .insert(RespType::SimpleString(SimpleString::new(b"foo")))
+            .insert(RespType::Integer(Integer::new(1)))
+            .build();
+        assert_eq!(set.bytes(), Bytes::from_static(b"~2\r\n+foo\r\n:1\r\n"));
+    }
+
+    #[test]
+    fn test_parse() {
+        let string = "~2\r\n+foo\r\n:1\r\n+bar\r\n";
+        let mut cursor = 0;
+        let set = Set::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        assert_eq!(set.bytes(), Bytes::from_static(b"~2\r\n+foo\r\n:1\r\n"));
+        assert_eq!(cursor, 14);
+    }
+
+    #[test]
+    fn test_while_valid_incomplete() {
+        let string = "~2\r\n+foo\r\n";
+        assert_eq!(
+            Set::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+            RespErrorKind::Incomplete { needed: None }
+        );
+    }
+}
@@ -0,0 +1,313 @@
+use crate::{aggregate, length, Limits, RespType};
+use bytes::{BufMut, Bytes, BytesMut};
+
+/// Part of a streamed value (RESP3)
+///
+/// Streamed values are sent when their length is not known up front: a header,
+/// then chunks of a string or values of an aggregate, then an end marker.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Streamed(Bytes);
+
+impl Streamed {
+    /// Header of a streamed Bulk String, `$?\r\n`
+    ///
+    /// # Example
+    /// ```
+    /// use bytes::{BufMut, BytesMut};
+    /// use resp_protocol::Streamed;
+    ///
+    /// let mut bytes = BytesMut::new();
+    /// bytes.put(Streamed::bulk_string_header().bytes());
+    /// bytes.put(Streamed::chunk(b"Hello ").bytes());
+    /// bytes.put(Streamed::chunk(b"world").bytes());
+    /// bytes.put(Streamed::bulk_string_end().bytes());
+    /// assert_eq!(bytes, &b"$?\r\n;6\r\nHello \r\n;5\r\nworld\r\n;0\r\n"[..]);
+    /// ```
+    #[inline]
+    pub fn bulk_string_header() -> Self {
+        Self(Bytes::from_static(b"$?\r\n"))
+    }
+
+    /// Chunk of a streamed Bulk String, `;<length>\r\n<value>\r\n`
+    ///
+    /// An empty chunk ends the streamed Bulk String.
+    pub fn chunk(input: &[u8]) -> Self {
+        if input.is_empty() {
+            return Self::bulk_string_end();
+        }
+        let length_string = input.len().to_string();
+        let mut bytes = BytesMut::with_capacity(input.len() + length_string.len() + 5);
+        bytes.put_u8(0x3b); // ";"
+        bytes.put_slice(length_string.as_bytes());
+        bytes.put_u8(0x0d); // CR
+        bytes.put_u8(0x0a); // LF
+        bytes.put_slice(input);
+        bytes.put_u8(0x0d); // CR
+        bytes.put_u8(0x0a); // LF
+        Self(bytes.freeze())
+    }
+
+    /// End of a streamed Bulk String, `;0\r\n`
+    #[inline]
+    pub fn bulk_string_end() -> Self {
+        Self(Bytes::from_static(b";0\r\n"))
+    }
+
+    /// Header of a streamed Array, `*?\r\n`
+    ///
+    /// # Example
+    /// ```
+    /// use bytes::{BufMut, BytesMut};
+    /// use resp_protocol::{Integer, Streamed};
+    ///
+    /// let mut bytes = BytesMut::new();
+    /// bytes.put(Streamed::array_header().bytes());
+    /// bytes.put(Integer::new(1).bytes());
+    /// bytes.put(Streamed::aggregate_end().bytes());
+    /// assert_eq!(bytes, &b"*?\r\n:1\r\n.\r\n"[..]);
+    /// ```
+    #[inline]
+    pub fn array_header() -> Self {
+        Self(Bytes::from_static(b"*?\r\n"))
+    }
+
+    /// Header of a streamed Map, `%?\r\n`
+    #[inline]
+    pub fn map_header() -> Self {
+        Self(Bytes::from_static(b"%?\r\n"))
+    }
+
+    /// Header of a streamed Set, `~?\r\n`
+    #[inline]
+    pub fn set_header() -> Self {
+        Self(Bytes::from_static(b"~?\r\n"))
+    }
+
+    /// End of a streamed Array, Map or Set, `.\r\n`
+    #[inline]
+    pub fn aggregate_end() -> Self {
+        Self(Bytes::from_static(b".\r\n"))
+    }
+
+    #[inline]
+    pub fn bytes(&self) -> Bytes {
+        self.0.clone()
+    }
+
+    #[allow(clippy::len_without_is_empty)]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// Copy a single validated value, reassembling streamed strings and aggregates
+/// into their length-prefixed forms
+pub(crate) fn reassemble(input: &[u8]) -> Bytes {
+    let mut bytes = BytesMut::with_capacity(input.len());
+    let mut index = 0;
+    put_value(input, &mut index, &mut bytes);
+    bytes.freeze()
+}
+
+/// Copy a single validated value, reassembling it only if validating it found it `streamed`
+#[inline]
+pub(crate) fn copy(input: &[u8], streamed: bool) -> Bytes {
+    if streamed {
+        reassemble(input)
+    } else {
+        Bytes::copy_from_slice(input)
+    }
+}
+
+/// Like [`copy`], but keeps `input` as is unless it holds a streamed value
+#[inline]
+pub(crate) fn reassemble_bytes(input: Bytes, streamed: bool) -> Bytes {
+    if streamed {
+        reassemble(&input)
+    } else {
+        input
+    }
+}
+
+/// Aggregate opened while reassembling a value
+enum Open {
+    /// Values of an aggregate left to copy after its header.
+    Values(usize),
+    /// Streamed aggregate, with its values reassembled so far.
+    Streamed {
+        first_char: u8,
+        count: usize,
+        values: BytesMut,
+    },
+}
+
+/// Copy the value at `index`, tracking nested aggregates on an explicit stack
+fn put_value(input: &[u8], index: &mut usize, bytes: &mut BytesMut) {
+    let end = input.len();
+    let mut stack = Vec::new();
+    loop {
+        if matches!(stack.last(), Some(Open::Streamed { .. })) && input[*index] == 0x2e {
+            *index += 3;
+            if let Some(Open::Streamed {
+                first_char,
+                count,
+                values,
+            }) = stack.pop()
+            {
+                let output = output(&mut stack, bytes);
+                put_header(first_char, aggregate::entries(first_char, count), output);
+                output.put(values);
+            }
+        } else {
+            let first_char = input[*index];
+            let output = output(&mut stack, bytes);
+            if first_char == 0x24 && input[*index + 1] == 0x3f {
+                put_chunks(input, index, output);
+            } else if aggregate::is_aggregate(first_char) {
+                let start = *index;
+                let header = aggregate::while_valid_header(
+                    first_char,
+                    input,
+                    index,
+                    &end,
+                    &Limits::unlimited(),
+                )
+                .expect("streamed value is valid");
+                match header {
+                    Some(0) => output.put_slice(&input[start..*index]),
+                    Some(count) => {
+                        output.put_slice(&input[start..*index]);
+                        stack.push(Open::Values(count));
+                        continue;
+                    }
+                    None => {
+                        stack.push(Open::Streamed {
+                            first_char,
+                            count: 0,
+                            values: BytesMut::new(),
+                        });
+                        continue;
+                    }
+                }
+            } else {
+                let start = *index;
+                RespType::while_valid_with(input, index, &end, &Limits::unlimited())
+                    .expect("streamed value is valid");
+                output.put_slice(&input[start..*index]);
+            }
+        }
+        loop {
+            match stack.last_mut() {
+                None => return,
+                Some(Open::Values(remaining)) => {
+                    *remaining -= 1;
+                    if *remaining > 0 {
+                        break;
+                    }
+                    stack.pop();
+                }
+                Some(Open::Streamed { count, .. }) => {
+                    *count += 1;
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Buffer the next value is copied to, the innermost streamed aggregate if any
+fn output<'a>(stack: &'a mut [Open], bytes: &'a mut BytesMut) -> &'a mut BytesMut {
+    stack
+        .iter_mut()
+        .rev()
+        .find_map(|open| match open {
+            Open::Streamed { values, .. } => Some(values),
+            Open::Values(_) => None,
+        })
+        .unwrap_or(bytes)
+}
+
+/// Copy the streamed string at `index` as a Bulk String
+fn put_chunks(input: &[u8], index: &mut usize, bytes: &mut BytesMut) {
+    let end = input.len();
+    *index += 4;
+    let mut value = BytesMut::new();
+    loop {
+        let mut chunk_index = *index + 1;
+        let length =
+            length::while_valid(input, &mut chunk_index, &end).expect("streamed value is valid");
+        chunk_index += 2;
+        if length == 0 {
+            *index = chunk_index;
+            break;
+        }
+        value.put_slice(&input[chunk_index..chunk_index + length]);
+        *index = chunk_index + length + 2;
+    }
+    put_header(0x24, value.len(), bytes);
+    bytes.put(value);
+    bytes.put_u8(0x0d); // CR
+    bytes.put_u8(0x0a); // LF
+}
+
+fn put_header(first_char: u8, length: usize, bytes: &mut BytesMut) {
+    bytes.put_u8(first_char);
+    bytes.put_slice(length.to_string().as_bytes());
+    bytes.put_u8(0x0d); // CR
+    bytes.put_u8(0x0a); // LF
+}
+
+#[cfg(test)]
+mod tests_streamed {
+    use crate::streamed::reassemble;
+    use crate::Streamed;
+    use bytes::Bytes;
+
+    #[test]
+    fn test_chunk() {
+        assert_eq!(
+            Streamed::chunk(b"foo").bytes(),
+            Bytes::from_static(b";3\r\nfoo\r\n")
+        );
+        assert_eq!(Streamed::chunk(b""), Streamed::bulk_string_end());
+    }
+
+    #[test]
+    fn test_reassemble_bulk_string() {
+        assert_eq!(
+            reassemble(b"$?\r\n;4\r\nHell\r\n;5\r\no wor\r\n;1\r\nd\r\n;0\r\n"),
+            Bytes::from_static(b"$10\r\nHello word\r\n")
+        );
+        assert_eq!(
+            reassemble(b"$?\r\n;0\r\n"),
+            Bytes::from_static(b"$0\r\n\r\n")
+        );
+    }
+
+    #[test]
+    fn test_reassemble_aggregates() {
+        assert_eq!(
+            reassemble(b"*?\r\n:1\r\n$?\r\n;3\r\nfoo\r\n;0\r\n~?\r\n.\r\n.\r\n"),
+            Bytes::from_static(b"*3\r\n:1\r\n$3\r\nfoo\r\n~0\r\n")
+        );
+        assert_eq!(
+            reassemble(b"%?\r\n+a\r\n:1\r\n+b\r\n*2\r\n:2\r\n$?\r\n;1\r\nc\r\n;0\r\n.\r\n"),
+            Bytes::from_static(b"%2\r\n+a\r\n:1\r\n+b\r\n*2\r\n:2\r\n$1\r\nc\r\n")
+        );
+    }
+
+    #[test]
+    fn test_reassemble_plain_value() {
+        let input = b"*2\r\n$3\r\nfoo\r\n:1\r\n";
+        assert_eq!(reassemble(input), Bytes::from_static(input));
+    }
+
+    #[test]
+    fn test_reassemble_deep_nesting() {
+        let depth = 100_000;
+        let input = format!("{}:1\r\n{}", "*?\r\n".repeat(depth), ".\r\n".repeat(depth));
+        let expected = format!("{}:1\r\n", "*1\r\n".repeat(depth));
+        assert_eq!(reassemble(input.as_bytes()), Bytes::from(expected));
+    }
+}
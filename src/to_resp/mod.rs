@@ -0,0 +1,375 @@
+use crate::{
+    ArrayBuilder, BigNumber, Boolean, BulkString, Double, Integer, MapBuilder, Null, Protocol,
+    RespType, ToRespArg, NULL_BULK_STRING,
+};
+use bytes::Bytes;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+
+/// Conversion into a RESP value, for the given version of the protocol
+///
+/// Implemented for strings, bytes, numbers and booleans, and for `Option`, `Vec`, `HashMap`
+/// and `BTreeMap` of convertible types. `u8` is left out, so that `Vec<u8>` is written as the
+/// bytes of a string.
+///
+/// # Example
+/// ``` rust
+/// use resp_protocol::{Protocol, ToResp};
+/// use std::collections::BTreeMap;
+///
+/// let mut map = BTreeMap::new();
+/// map.insert("a", true);
+/// assert_eq!(map.to_resp(Protocol::Resp3).bytes(), &b"%1\r\n$1\r\na\r\n#t\r\n"[..]);
+/// assert_eq!(map.to_resp(Protocol::Resp2).bytes(), &b"*2\r\n$1\r\na\r\n:1\r\n"[..]);
+/// ```
+pub trait ToResp {
+    fn to_resp(&self, protocol: Protocol) -> RespType;
+}
+
+/// Null, as a Null Bulk String in RESP2
+pub(crate) fn null(protocol: Protocol) -> RespType {
+    match protocol {
+        Protocol::Resp2 => RespType::BulkString(NULL_BULK_STRING),
+        Protocol::Resp3 => RespType::Null(Null::new()),
+    }
+}
+
+/// Integer beyond `i64`, as a Bulk String of its digits in RESP2
+pub(crate) fn big_number(protocol: Protocol, digits: &str) -> RespType {
+    match protocol {
+        Protocol::Resp2 => RespType::BulkString(BulkString::new(digits.as_bytes())),
+        Protocol::Resp3 => RespType::BigNumber(BigNumber::new(digits.as_bytes())),
+    }
+}
+
+/// Map, as an Array of keys and values in RESP2
+pub fn map<I>(protocol: Protocol, entries: I) -> RespType
+where
+    I: IntoIterator<Item = (RespType, RespType)>,
+{
+    match protocol {
+        Protocol::Resp2 => {
+            let mut array_builder = ArrayBuilder::new();
+            for (key, value) in entries {
+                array_builder.insert(key).insert(value);
+            }
+            RespType::Array(array_builder.build())
+        }
+        Protocol::Resp3 => {
+            let mut map_builder = MapBuilder::new();
+            for (key, value) in entries {
+                map_builder.insert(key, value);
+            }
+            RespType::Map(map_builder.build())
+        }
+    }
+}
+
+/// Array of the given values
+pub fn array<I: IntoIterator<Item = RespType>>(values: I) -> RespType {
+    let mut array_builder = ArrayBuilder::new();
+    for value in values {
+        array_builder.insert(value);
+    }
+    RespType::Array(array_builder.build())
+}
+
+impl<T: ToResp + ?Sized> ToResp for &T {
+    #[inline]
+    fn to_resp(&self, protocol: Protocol) -> RespType {
+        (**self).to_resp(protocol)
+    }
+}
+
+impl ToResp for RespType {
+    #[inline]
+    fn to_resp(&self, _protocol: Protocol) -> RespType {
+        self.clone()
+    }
+}
+
+macro_rules! impl_to_resp_string {
+    ($($type:ty),*) => {
+        $(
+            impl ToResp for $type {
+                #[inline]
+                fn to_resp(&self, _protocol: Protocol) -> RespType {
+                    RespType::BulkString(BulkString::new(&self.to_resp_arg()))
+                }
+            }
+        )*
+    };
+}
+
+impl_to_resp_string!(str, String, [u8], Vec<u8>, Bytes);
+
+/// Written as an Integer `0` or `1` in RESP2
+impl ToResp for bool {
+    fn to_resp(&self, protocol: Protocol) -> RespType {
+        match protocol {
+            Protocol::Resp2 => RespType::Integer(Integer::new(*self as i64)),
+            Protocol::Resp3 => RespType::Boolean(Boolean::new(*self)),
+        }
+    }
+}
+
+macro_rules! impl_to_resp_integer {
+    ($($type:ty),*) => {
+        $(
+            /// Written as a Big number beyond `i64`, or a Bulk String in RESP2
+            impl ToResp for $type {
+                fn to_resp(&self, protocol: Protocol) -> RespType {
+                    match i64::try_from(*self) {
+                        Ok(value) => RespType::Integer(Integer::new(value)),
+                        Err(_) => big_number(protocol, &self.to_string()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_to_resp_integer!(i8, i16, i32, i64, i128, isize, u16, u32, u64, u128, usize);
+
+macro_rules! impl_to_resp_float {
+    ($($type:ty),*) => {
+        $(
+            /// Written as a Bulk String in RESP2
+            impl ToResp for $type {
+                fn to_resp(&self, protocol: Protocol) -> RespType {
+                    match protocol {
+                        Protocol::Resp2 => {
+                            RespType::BulkString(BulkString::new(&self.to_resp_arg()))
+                        }
+                        Protocol::Resp3 => RespType::Double(Double::from_float(*self)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_to_resp_float!(f32, f64);
+
+/// `None` is written as a Null, or a Null Bulk String in RESP2
+impl<T: ToResp> ToResp for Option<T> {
+    fn to_resp(&self, protocol: Protocol) -> RespType {
+        match self {
+            Some(value) => value.to_resp(protocol),
+            None => null(protocol),
+        }
+    }
+}
+
+impl<T: ToResp> ToResp for Vec<T> {
+    fn to_resp(&self, protocol: Protocol) -> RespType {
+        array(self.iter().map(|value| value.to_resp(protocol)))
+    }
+}
+
+/// Written as a Map, or an Array of keys and values in RESP2
+impl<K: ToResp, V: ToResp, S> ToResp for HashMap<K, V, S> {
+    fn to_resp(&self, protocol: Protocol) -> RespType {
+        map(
+            protocol,
+            self.iter()
+                .map(|(key, value)| (key.to_resp(protocol), value.to_resp(protocol))),
+        )
+    }
+}
+
+/// Written as a Map, or an Array of keys and values in RESP2
+impl<K: ToResp, V: ToResp> ToResp for BTreeMap<K, V> {
+    fn to_resp(&self, protocol: Protocol) -> RespType {
+        map(
+            protocol,
+            self.iter()
+                .map(|(key, value)| (key.to_resp(protocol), value.to_resp(protocol))),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests_to_resp {
+    use crate::{FromResp, Protocol, RespType, ToResp};
+    use bytes::Bytes;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_scalars() {
+        assert_eq!(
+            "foo".to_resp(Protocol::Resp3).bytes(),
+            Bytes::from_static(b"$3\r\nfoo\r\n")
+        );
+        assert_eq!(
+            u64::MAX.to_resp(Protocol::Resp3).bytes(),
+            Bytes::from_static(b"(18446744073709551615\r\n")
+        );
+        assert_eq!(
+            u64::MAX.to_resp(Protocol::Resp2).bytes(),
+            Bytes::from_static(b"$20\r\n18446744073709551615\r\n")
+        );
+        assert_eq!(
+            1.5_f64.to_resp(Protocol::Resp2).bytes(),
+            Bytes::from_static(b"$3\r\n1.5\r\n")
+        );
+        assert_eq!(
+            1.1_f32.to_resp(Protocol::Resp3).bytes(),
+            Bytes::from_static(b",1.1\r\n")
+        );
+        assert_eq!(
+            None::<i64>.to_resp(Protocol::Resp2).bytes(),
+            Bytes::from_static(b"$-1\r\n")
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut map = HashMap::new();
+        map.insert("a".to_owned(), vec![Some(1_i64), None]);
+        for protocol in [Protocol::Resp2, Protocol::Resp3] {
+            let value: RespType = map.to_resp(protocol);
+            assert_eq!(HashMap::from_resp(&value), Ok(map.clone()));
+        }
+    }
+}
+
+#[cfg(all(test, feature = "derive"))]
+mod tests_derive {
+    use crate::{FromResp, FromRespError, Protocol, RespType, ToResp};
+    use bytes::Bytes;
+
+    #[derive(Debug, PartialEq, ToResp, FromResp)]
+    struct Client {
+        id: u64,
+        #[resp(rename = "lib-name")]
+        lib_name: Option<String>,
+        #[resp(skip)]
+        cached: bool,
+    }
+
+    #[derive(Debug, PartialEq, ToResp, FromResp)]
+    #[resp(flatten)]
+    struct Entry {
+        r#type: String,
+        score: f64,
+    }
+
+    #[derive(Debug, PartialEq, ToResp, FromResp)]
+    struct Pair(String, i64);
+
+    #[derive(Debug, PartialEq, ToResp, FromResp)]
+    struct Key(String);
+
+    #[derive(Debug, PartialEq, ToResp, FromResp)]
+    struct Reply<T> {
+        values: Vec<T>,
+    }
+
+    #[test]
+    fn test_to_resp() {
+        let client = Client {
+            id: 7,
+            lib_name: Some("redis-rs".to_owned()),
+            cached: true,
+        };
+        assert_eq!(
+            client.to_resp(Protocol::Resp3).bytes(),
+            Bytes::from_static(b"%2\r\n$2\r\nid\r\n:7\r\n$8\r\nlib-name\r\n$8\r\nredis-rs\r\n")
+        );
+        assert_eq!(
+            client.to_resp(Protocol::Resp2).bytes(),
+            Bytes::from_static(b"*4\r\n$2\r\nid\r\n:7\r\n$8\r\nlib-name\r\n$8\r\nredis-rs\r\n")
+        );
+        let entry = Entry {
+            r#type: "zset".to_owned(),
+            score: 1.5,
+        };
+        assert_eq!(
+            entry.to_resp(Protocol::Resp3).bytes(),
+            Bytes::from_static(b"*4\r\n$4\r\ntype\r\n$4\r\nzset\r\n$5\r\nscore\r\n,1.5\r\n")
+        );
+        assert_eq!(
+            Pair("a".to_owned(), 1).to_resp(Protocol::Resp3).bytes(),
+            Bytes::from_static(b"*2\r\n$1\r\na\r\n:1\r\n")
+        );
+        assert_eq!(
+            Key("a".to_owned()).to_resp(Protocol::Resp3).bytes(),
+            Bytes::from_static(b"$1\r\na\r\n")
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let client = Client {
+            id: 7,
+            lib_name: None,
+            cached: false,
+        };
+        let reply = Reply {
+            values: vec![Pair("a".to_owned(), 1), Pair("b".to_owned(), 2)],
+        };
+        let entry = Entry {
+            r#type: "zset".to_owned(),
+            score: 1.5,
+        };
+        for protocol in [Protocol::Resp2, Protocol::Resp3] {
+            assert_eq!(
+                Client::from_resp(&client.to_resp(protocol)).as_ref(),
+                Ok(&client)
+            );
+            assert_eq!(
+                Reply::from_resp(&reply.to_resp(protocol)).as_ref(),
+                Ok(&reply)
+            );
+            assert_eq!(
+                Entry::from_resp(&entry.to_resp(protocol)).as_ref(),
+                Ok(&entry)
+            );
+            assert_eq!(
+                Key::from_resp(&Key("a".to_owned()).to_resp(protocol)),
+                Ok(Key("a".to_owned()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let value = RespType::parse_bytes(
+            &Bytes::from_static(b"*4\r\n$2\r\nid\r\n$3\r\ntwo\r\n$5\r\nextra\r\n:1\r\n"),
+            &mut 0,
+        )
+        .unwrap();
+        let error = Client::from_resp(&value).unwrap_err();
+        assert_eq!(error.path(), &[1]);
+        assert_eq!(
+            error.to_string(),
+            "Expected u64, found BulkString \"two\" at [1]."
+        );
+
+        let value = RespType::parse_bytes(
+            &Bytes::from_static(b"*2\r\n$8\r\nlib-name\r\n$1\r\nx\r\n"),
+            &mut 0,
+        )
+        .unwrap();
+        assert_eq!(
+            Client::from_resp(&value),
+            Err(FromRespError::missing("field `id`"))
+        );
+
+        let value =
+            RespType::parse_bytes(&Bytes::from_static(b"*1\r\n$1\r\na\r\n"), &mut 0).unwrap();
+        assert_eq!(
+            Pair::from_resp(&value).unwrap_err().to_string(),
+            "Expected Pair, found Array."
+        );
+
+        let value = RespType::parse_bytes(
+            &Bytes::from_static(b"*1\r\n*2\r\n$1\r\na\r\n$1\r\nb\r\n"),
+            &mut 0,
+        )
+        .unwrap();
+        let error = Reply::<Pair>::from_resp(&value).unwrap_err();
+        assert_eq!(error.to_string(), "Expected Reply, found Array.");
+    }
+}
@@ -0,0 +1,269 @@
+use crate::{blob, Limits, RespError, RespErrorKind};
+use bytes::{BufMut, Bytes, BytesMut};
+use std::convert::TryFrom;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerbatimString(pub(crate) Bytes);
+
+/// Verbatim string type (RESP3)
+impl VerbatimString {
+    /// Build a new Verbatim string
+    ///
+    /// # Example
+    /// ```
+    /// use resp_protocol::VerbatimString;
+    ///
+    /// let verbatim_string = VerbatimString::new(b"txt", b"Some string");
+    /// println!("{:?}", verbatim_string); // VerbatimString(b"=15\r\ntxt:Some string\r\n")
+    /// ```
+    pub fn new(format: &[u8; 3], input: &[u8]) -> Self {
+        let length_string = (input.len() + 4).to_string();
+        let mut bytes = BytesMut::with_capacity(input.len() + length_string.len() + 9);
+        bytes.put_u8(0x3d); // "="
+        bytes.put_slice(length_string.as_bytes());
+        bytes.put_u8(0x0d); // CR
+        bytes.put_u8(0x0a); // LF
+        bytes.put_slice(format);
+        bytes.put_u8(0x3a); // ":"
+        bytes.put_slice(input);
+        bytes.put_u8(0x0d); // CR
+        bytes.put_u8(0x0a); // LF
+        Self::from_bytes_unchecked(bytes.freeze())
+    }
+
+    #[inline]
+    pub fn bytes(&self) -> Bytes {
+        self.0.clone()
+    }
+
+    #[allow(clippy::len_without_is_empty)]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    fn value_start_index(&self) -> usize {
+        let mut index = 1;
+        while self.0[index] != 0x0d {
+            index += 1;
+        }
+        index + 2
+    }
+
+    /// Format of the value, e.g. `txt` or `mkd`
+    ///
+    /// ``` rust
+    /// use resp_protocol::VerbatimString;
+    ///
+    /// let verbatim_string: VerbatimString = VerbatimString::new(b"mkd", b"# Title");
+    /// assert_eq!(verbatim_string.format(), b"mkd");
+    /// ```
+    #[inline]
+    pub fn format(&self) -> &[u8] {
+        let value_start_index = self.value_start_index();
+        &self.0[value_start_index..value_start_index + 3]
+    }
+
+    ///
+    ///
+    /// ``` rust
+    /// use resp_protocol::VerbatimString;
+    ///
+    /// let verbatim_string: VerbatimString = VerbatimString::new(b"txt", b"Some string");
+    /// assert_eq!(verbatim_string.value(), "Some string");
+    /// ```
+    #[inline]
+    pub fn value(&self) -> Bytes {
+        self.0.slice(self.value_start_index() + 4..self.0.len() - 2)
+    }
+
+    #[inline]
+    pub fn value_len(&self) -> usize {
+        self.0.len() - 6 - self.value_start_index()
+    }
+
+    #[inline]
+    pub fn from_bytes_unchecked(input: Bytes) -> Self {
+        Self(input)
+    }
+
+    #[inline]
+    pub fn from_slice_unchecked(input: &[u8]) -> Self {
+        let bytes = Bytes::copy_from_slice(input);
+        Self::from_bytes_unchecked(bytes)
+    }
+
+    /// Build a new VerbatimString from `input`, checking that it holds exactly one valid value
+    ///
+    /// # Example
+    /// ``` rust
+    /// use resp_protocol::{VerbatimString, RespErrorKind};
+    /// use bytes::Bytes;
+    ///
+    /// assert!(VerbatimString::try_from_bytes(Bytes::from_static(b"=7\r\ntxt:foo\r\n")).is_ok());
+    /// assert_eq!(
+    ///     VerbatimString::try_from_bytes(Bytes::from_static(b"=7\r\ntxt:foo\r\n\r\n")).unwrap_err(),
+    ///     RespErrorKind::TrailingBytes
+    /// );
+    /// ```
+    pub fn try_from_bytes(input: Bytes) -> Result<Self, RespError> {
+        let mut index = 0;
+        Self::while_valid(&input, &mut index, &input.len())?;
+        if index != input.len() {
+            return Err(RespError::new(
+                RespErrorKind::TrailingBytes,
+                &input,
+                &input.len(),
+                index,
+            ));
+        }
+        Ok(Self::from_bytes_unchecked(input))
+    }
+
+    #[inline]
+    pub fn try_from_slice(input: &[u8]) -> Result<Self, RespError> {
+        Self::try_from_bytes(Bytes::copy_from_slice(input))
+    }
+
+    pub fn while_valid(input: &[u8], start: &mut usize, end: &usize) -> Result<(), RespError> {
+        Self::while_valid_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Validate a Verbatim String declaring no more than [`Limits::max_bulk_length`] bytes
+    pub fn while_valid_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<(), RespError> {
+        limits.while_valid_frame(input, start, end, |input, start, end| {
+            Self::while_valid_nested(input, start, end, limits)
+        })
+    }
+
+    pub(crate) fn while_valid_nested(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<(), RespError> {
+        let mut index = *start;
+        blob::while_valid(0x3d, input, &mut index, end, limits)?;
+        let mut value_start_index = *start + 1;
+        while input[value_start_index] != 0x0d {
+            value_start_index += 1;
+        }
+        value_start_index += 2;
+        if index - 2 - value_start_index < 4 || input[value_start_index + 3] != 0x3a {
+            let format_end_index = (value_start_index + 3).min(index - 2);
+            return Err(
+                RespError::new(RespErrorKind::InvalidValue, input, end, format_end_index)
+                    .expecting(":")
+                    .within(0x3d),
+            );
+        }
+        *start = index;
+        Ok(())
+    }
+
+    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, RespError> {
+        Self::parse_with(input, start, end, &Limits::unlimited())
+    }
+
+    /// Parse a Verbatim String declaring no more than [`Limits::max_bulk_length`] bytes
+    pub fn parse_with(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        Self::while_valid_with(input, &mut index, end, limits)?;
+        let value = Self::from_slice_unchecked(&input[*start..index]);
+        *start = index;
+        Ok(value)
+    }
+
+    /// Parse a Verbatim String from `input`, sharing its buffer instead of copying it
+    pub fn parse_bytes(input: &Bytes, start: &mut usize) -> Result<Self, RespError> {
+        Self::parse_bytes_with(input, start, &Limits::unlimited())
+    }
+
+    /// Parse a Verbatim String from `input` like [`VerbatimString::parse_bytes`], declaring no more
+    /// than [`Limits::max_bulk_length`] bytes
+    pub fn parse_bytes_with(
+        input: &Bytes,
+        start: &mut usize,
+        limits: &Limits,
+    ) -> Result<Self, RespError> {
+        let mut index = *start;
+        Self::while_valid_with(input, &mut index, &input.len(), limits)?;
+        let value = Self::from_bytes_unchecked(input.slice(*start..index));
+        *start = index;
+        Ok(value)
+    }
+}
+
+impl TryFrom<Bytes> for VerbatimString {
+    type Error = RespError;
+
+    fn try_from(input: Bytes) -> Result<Self, RespError> {
+        Self::try_from_bytes(input)
+    }
+}
+
+#[cfg(test)]
+mod tests_verbatim_string {
+    use crate::verbatim_string::VerbatimString;
+    use crate::RespErrorKind;
+    use bytes::Bytes;
+
+    #[test]
+    fn test_new() {
+        let verbatim_string = VerbatimString::new(b"txt", b"Some string");
+        assert_eq!(
+            verbatim_string,
+            VerbatimString(Bytes::from_static(b"=15\r\ntxt:Some string\r\n"))
+        );
+    }
+
+    #[test]
+    fn test_format_and_value() {
+        let verbatim_string = VerbatimString(Bytes::from_static(b"=10\r\nmkd:a\r\nb\r\n\r\n"));
+        assert_eq!(verbatim_string.format(), b"mkd");
+        assert_eq!(verbatim_string.value(), Bytes::from_static(b"a\r\nb\r\n"));
+        assert_eq!(verbatim_string.value_len(), 6);
+    }
+
+    #[test]
+    fn test_parse() {
+        let string = "=15\r\ntxt:Some string\r\n+bar\r\n";
+        let mut cursor = 0;
+        assert_eq!(
+            VerbatimString::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap(),
+            VerbatimString::new(b"txt", b"Some string")
+        );
+        assert_eq!(cursor, 22);
+    }
+
+    #[test]
+    fn test_parse_empty_value() {
+        let string = "=4\r\ntxt:\r\n";
+        let verbatim_string =
+            VerbatimString::parse(string.as_bytes(), &mut 0, &string.len()).unwrap();
+        assert_eq!(verbatim_string.value(), Bytes::new());
+    }
+
+    #[test]
+    fn test_while_valid_invalid_format() {
+        for string in ["=3\r\ntxt\r\n", "=5\r\ntxt-a\r\n"] {
+            assert_eq!(
+                VerbatimString::while_valid(string.as_bytes(), &mut 0, &string.len()).unwrap_err(),
+                RespErrorKind::InvalidValue,
+                "{:?}",
+                string
+            );
+        }
+    }
+}